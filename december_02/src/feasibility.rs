@@ -0,0 +1,173 @@
+use crate::parser::{Game, GameSample};
+use clap::ValueEnum;
+
+/// Quantity minimised when searching for the smallest bag.
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Objective {
+    /// Total number of cubes in the bag
+    Total,
+    /// Product of the colour counts
+    Power,
+    /// Count of the most numerous colour
+    Largest,
+}
+
+impl Objective {
+    pub fn score(&self, bag: &GameSample) -> u32 {
+        match self {
+            Objective::Total => bag.num_cubes(),
+            Objective::Power => bag.power(),
+            Objective::Largest => bag.red().max(bag.green()).max(bag.blue()),
+        }
+    }
+}
+
+/// Candidate bags are the grid spanned by the per-colour counts of the minimum bags, as a
+/// feasible bag can always be shrunk to that grid without invalidating any game.
+struct CandidateGrid {
+    reds: Vec<u32>,
+    greens: Vec<u32>,
+    blues: Vec<u32>,
+    num_valid: Vec<usize>,
+}
+
+impl CandidateGrid {
+    fn new(games: &[Game]) -> Self {
+        let minimum_bags = games
+            .iter()
+            .map(Game::minimum_bag)
+            .collect::<Vec<GameSample>>();
+        let axis = |colour: fn(&GameSample) -> u32| {
+            let mut values = minimum_bags.iter().map(colour).collect::<Vec<u32>>();
+            values.push(0);
+            values.sort();
+            values.dedup();
+            values
+        };
+        let reds = axis(GameSample::red);
+        let greens = axis(GameSample::green);
+        let blues = axis(GameSample::blue);
+
+        let mut num_valid = Vec::with_capacity(reds.len() * greens.len() * blues.len());
+        for &red in &reds {
+            for &green in &greens {
+                for &blue in &blues {
+                    let bag = GameSample::new(red, green, blue);
+                    num_valid.push(
+                        minimum_bags
+                            .iter()
+                            .filter(|min| min.fits_into(&bag))
+                            .count(),
+                    );
+                }
+            }
+        }
+        CandidateGrid {
+            reds,
+            greens,
+            blues,
+            num_valid,
+        }
+    }
+
+    fn index(&self, r: usize, g: usize, b: usize) -> usize {
+        (r * self.greens.len() + g) * self.blues.len() + b
+    }
+
+    fn is_feasible(&self, r: usize, g: usize, b: usize, min_valid: usize) -> bool {
+        self.num_valid[self.index(r, g, b)] >= min_valid
+    }
+
+    fn feasible_bags(&self, min_valid: usize) -> impl Iterator<Item = (usize, usize, usize)> + '_ {
+        (0..self.reds.len())
+            .flat_map(move |r| (0..self.greens.len()).map(move |g| (r, g)))
+            .flat_map(move |(r, g)| (0..self.blues.len()).map(move |b| (r, g, b)))
+            .filter(move |&(r, g, b)| self.is_feasible(r, g, b, min_valid))
+    }
+
+    fn bag(&self, r: usize, g: usize, b: usize) -> GameSample {
+        GameSample::new(self.reds[r], self.greens[g], self.blues[b])
+    }
+}
+
+/// Smallest bag, according to `objective`, for which at least `min_valid` games are valid.
+pub fn smallest_bag(games: &[Game], min_valid: usize, objective: Objective) -> Option<GameSample> {
+    if min_valid > games.len() {
+        return None;
+    }
+    let grid = CandidateGrid::new(games);
+    grid.feasible_bags(min_valid)
+        .map(|(r, g, b)| grid.bag(r, g, b))
+        .min_by_key(|bag| (objective.score(bag), bag.red(), bag.green(), bag.blue()))
+}
+
+/// All bags for which at least `min_valid` games are valid and which cannot lose a cube of
+/// any colour without dropping below `min_valid`.
+pub fn pareto_frontier(games: &[Game], min_valid: usize) -> Vec<GameSample> {
+    if min_valid > games.len() {
+        return Vec::new();
+    }
+    let grid = CandidateGrid::new(games);
+    grid.feasible_bags(min_valid)
+        .filter(|&(r, g, b)| {
+            (r == 0 || !grid.is_feasible(r - 1, g, b, min_valid))
+                && (g == 0 || !grid.is_feasible(r, g - 1, b, min_valid))
+                && (b == 0 || !grid.is_feasible(r, g, b - 1, min_valid))
+        })
+        .map(|(r, g, b)| grid.bag(r, g, b))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_games() -> Vec<Game> {
+        [
+            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
+            "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue",
+            "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
+            "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
+            "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
+        ]
+        .into_iter()
+        .map(Game::parse_game)
+        .collect()
+    }
+
+    #[test]
+    fn test_smallest_bag_all_games() {
+        let games = test_games();
+        assert_eq!(
+            smallest_bag(&games, games.len(), Objective::Total),
+            Some(GameSample::new(20, 13, 15))
+        );
+    }
+
+    #[test]
+    fn test_smallest_bag_some_games() {
+        let games = test_games();
+        assert_eq!(
+            smallest_bag(&games, 2, Objective::Total),
+            Some(GameSample::new(4, 3, 6))
+        );
+        assert_eq!(
+            smallest_bag(&games, 3, Objective::Largest),
+            Some(GameSample::new(6, 3, 6))
+        );
+        assert_eq!(smallest_bag(&games, 6, Objective::Total), None);
+    }
+
+    #[test]
+    fn test_pareto_frontier() {
+        let games = test_games();
+        assert_eq!(
+            pareto_frontier(&games, 4),
+            vec![GameSample::new(14, 3, 15), GameSample::new(20, 13, 6)]
+        );
+        assert_eq!(
+            pareto_frontier(&games, 5),
+            vec![GameSample::new(20, 13, 15)]
+        );
+    }
+}
@@ -1,33 +1,115 @@
-use std::fs::read_to_string;
+use crate::feasibility::{pareto_frontier, smallest_bag, Objective};
 use crate::parser::{Game, GameSample};
+use clap::{Parser, ValueEnum};
+use std::fs::read_to_string;
+use std::time::Instant;
 
+mod feasibility;
 mod parser;
 
-fn task_1() {
-    let game_sample = GameSample::new(12, 13, 14);
-
-    let sum_of_games: u32 = read_to_string("./input/input_part1.txt")
-            .unwrap()
-            .lines()
-            .map(Game::parse_game)
-        .filter(|game| game.is_valid(&game_sample))
-        .map(|game| game.game_id())
-        .sum();
-    println!("{}", sum_of_games)
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
+enum Tasks {
+    Task1,
+    Task2,
+    /// Find the smallest bag making at least `min_valid` games valid
+    SmallestBag,
+    /// List all minimal bags making at least `min_valid` games valid
+    Pareto,
+    /// Describe every game with respect to the bag used by task 1
+    Describe,
 }
 
+#[derive(Debug, Parser)]
+struct Cli {
+    /// Input file to read
+    input_file: String,
+    /// Task to run
+    #[structopt(short = 't')]
+    task: Tasks,
+    /// Red cubes in the bag used by task 1
+    #[arg(long, default_value_t = 12)]
+    red: u32,
+    /// Green cubes in the bag used by task 1
+    #[arg(long, default_value_t = 13)]
+    green: u32,
+    /// Blue cubes in the bag used by task 1
+    #[arg(long, default_value_t = 14)]
+    blue: u32,
+    /// Minimal number of valid games for the bag search, defaults to all games
+    #[arg(short = 'k', long)]
+    min_valid: Option<usize>,
+    /// Objective minimised by the bag search
+    #[arg(long, value_enum, default_value_t = Objective::Total)]
+    objective: Objective,
+}
 
-fn task_2() {
-    let sum_of_games: u32 = read_to_string("./input/input_part1.txt")
+fn load_games(input_file: &str) -> Vec<Game> {
+    read_to_string(input_file)
         .unwrap()
         .lines()
         .map(Game::parse_game)
-        .map(|game| game.power())
-        .sum();
-    println!("{}", sum_of_games)
+        .collect()
 }
 
+fn task_1(games: &[Game], game_sample: &GameSample) -> u32 {
+    games
+        .iter()
+        .filter(|game| game.is_valid(game_sample))
+        .map(|game| game.game_id())
+        .sum()
+}
+
+fn task_2(games: &[Game]) -> u32 {
+    games.iter().map(|game| game.power()).sum()
+}
+
+fn describe(games: &[Game], game_sample: &GameSample) -> String {
+    games
+        .iter()
+        .map(|game| {
+            let violation = match game.violating_sample(game_sample) {
+                Some(sample) => format!("impossible because of draw {}", sample),
+                None => "possible".to_owned(),
+            };
+            format!(
+                "Game {}: {} draws, minimum bag {}, totals {}, {}, {} bags of {} cubes make it possible",
+                game.game_id(),
+                game.samples().len(),
+                game.minimum_bag(),
+                game.totals(),
+                violation,
+                game.possible_bags(game_sample.num_cubes()).len(),
+                game_sample.num_cubes(),
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
 
 fn main() {
-    task_2();
-}
\ No newline at end of file
+    let args = Cli::parse();
+
+    let start = Instant::now();
+    let games = load_games(&args.input_file);
+    let game_sample = GameSample::new(args.red, args.green, args.blue);
+    let min_valid = args.min_valid.unwrap_or(games.len());
+    let result = match args.task {
+        Tasks::Task1 => task_1(&games, &game_sample).to_string(),
+        Tasks::Task2 => task_2(&games).to_string(),
+        Tasks::SmallestBag => match smallest_bag(&games, min_valid, args.objective) {
+            Some(bag) => bag.to_string(),
+            None => format!("no bag makes {} games valid", min_valid),
+        },
+        Tasks::Pareto => pareto_frontier(&games, min_valid)
+            .iter()
+            .map(GameSample::to_string)
+            .collect::<Vec<String>>()
+            .join("\n"),
+        Tasks::Describe => describe(&games, &game_sample),
+    };
+    println!(
+        "The task took {}ms to complete",
+        start.elapsed().as_nanos() as f32 / 1_000_000.0
+    );
+    println!("Result is {}", result);
+}
@@ -1,43 +1,75 @@
 use std::cmp::max;
+use std::fmt::{Display, Formatter};
 
 pub struct Game {
     game_id: u32,
-    red: u32,
-    green: u32,
-    blue: u32,
+    samples: Vec<GameSample>,
 }
 
 impl Game {
     pub fn game_id(&self) -> u32 {
         self.game_id
     }
+
+    pub fn samples(&self) -> &[GameSample] {
+        &self.samples
+    }
+
     pub fn parse_game(line: &str) -> Self {
         let (game_name, definition) = line.split_once(":").unwrap();
         let game_id: u32 = game_name.trim().split_once(" ").unwrap().1.parse().unwrap();
-        let game_sample_max: GameSample = definition
-            .split(";")
-            .map(GameSample::from_str)
-            .fold(GameSample::default(), GameSample::max);
-        Game {
-            game_id,
-            red: game_sample_max.red,
-            green: game_sample_max.green,
-            blue: game_sample_max.blue,
+        let samples: Vec<GameSample> = definition.split(";").map(GameSample::from_str).collect();
+        Game { game_id, samples }
+    }
+
+    /// Smallest bag which could have produced every draw of the game.
+    pub fn minimum_bag(&self) -> GameSample {
+        self.samples
+            .iter()
+            .copied()
+            .fold(GameSample::default(), GameSample::max)
+    }
+
+    /// First draw which could not have been taken from the given bag.
+    pub fn violating_sample(&self, game_sample: &GameSample) -> Option<&GameSample> {
+        self.samples
+            .iter()
+            .find(|sample| !sample.fits_into(game_sample))
+    }
+
+    /// Total number of cubes of each colour shown over all draws.
+    pub fn totals(&self) -> GameSample {
+        self.samples
+            .iter()
+            .copied()
+            .fold(GameSample::default(), GameSample::add)
+    }
+
+    /// All bags holding exactly `num_cubes` cubes which make this game possible.
+    pub fn possible_bags(&self, num_cubes: u32) -> Vec<GameSample> {
+        let minimum_bag = self.minimum_bag();
+        let mut bags = Vec::new();
+        for red in minimum_bag.red..=num_cubes {
+            for green in minimum_bag.green..=(num_cubes - red) {
+                let blue = num_cubes - red - green;
+                if blue >= minimum_bag.blue {
+                    bags.push(GameSample::new(red, green, blue));
+                }
+            }
         }
+        bags
     }
 
     pub fn is_valid(&self, game_sample: &GameSample) -> bool {
-        self.red <= game_sample.red
-            && self.green <= game_sample.green
-            && self.blue <= game_sample.blue
+        self.violating_sample(game_sample).is_none()
     }
 
     pub fn power(&self) -> u32 {
-        self.red * self.green * self.blue
+        self.minimum_bag().power()
     }
 }
 
-#[derive(Default)]
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
 pub struct GameSample {
     red: u32,
     green: u32,
@@ -48,6 +80,33 @@ impl GameSample {
     pub fn new(red: u32, green: u32, blue: u32) -> Self {
         GameSample { red, green, blue }
     }
+
+    pub fn red(&self) -> u32 {
+        self.red
+    }
+
+    pub fn green(&self) -> u32 {
+        self.green
+    }
+
+    pub fn blue(&self) -> u32 {
+        self.blue
+    }
+
+    pub fn num_cubes(&self) -> u32 {
+        self.red + self.green + self.blue
+    }
+
+    pub fn power(&self) -> u32 {
+        self.red * self.green * self.blue
+    }
+
+    pub fn fits_into(&self, game_sample: &GameSample) -> bool {
+        self.red <= game_sample.red
+            && self.green <= game_sample.green
+            && self.blue <= game_sample.blue
+    }
+
     fn from_str(sample_str: &str) -> Self {
         let mut red: u32 = 0;
         let mut green: u32 = 0;
@@ -73,6 +132,24 @@ impl GameSample {
             max(lhs_game.blue, rhs_game.blue),
         )
     }
+
+    fn add(lhs_game: GameSample, rhs_game: GameSample) -> GameSample {
+        GameSample::new(
+            lhs_game.red + rhs_game.red,
+            lhs_game.green + rhs_game.green,
+            lhs_game.blue + rhs_game.blue,
+        )
+    }
+}
+
+impl Display for GameSample {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} red, {} green, {} blue",
+            self.red, self.green, self.blue
+        )
+    }
 }
 
 #[cfg(test)]
@@ -120,18 +197,16 @@ mod tests {
             "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
         );
         assert_eq!(game.game_id, 3);
-        assert_eq!(game.red, 20);
-        assert_eq!(game.green, 13);
-        assert_eq!(game.blue, 6);
+        assert_eq!(game.samples.len(), 3);
+        assert_eq!(game.samples[1], GameSample::new(4, 13, 5));
+        assert_eq!(game.minimum_bag(), GameSample::new(20, 13, 6));
     }
 
     #[test]
     fn test_is_valid() {
         let game = Game {
             game_id: 1,
-            red: 10,
-            green: 12,
-            blue: 15,
+            samples: vec![GameSample::new(10, 2, 15), GameSample::new(1, 12, 3)],
         };
         assert!(game.is_valid(&GameSample {
             red: 30,
@@ -169,4 +244,30 @@ mod tests {
         assert_eq!(game_4.power(), 630);
         assert_eq!(game_5.power(), 36);
     }
+
+    #[test]
+    fn test_violating_sample() {
+        let game = Game::parse_game(
+            "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
+        );
+        let bag = GameSample::new(12, 13, 14);
+        assert_eq!(game.violating_sample(&bag), Some(&GameSample::new(20, 8, 6)));
+        assert_eq!(game.violating_sample(&GameSample::new(20, 13, 6)), None);
+    }
+
+    #[test]
+    fn test_totals() {
+        let game = Game::parse_game("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green");
+        assert_eq!(game.totals(), GameSample::new(5, 4, 9));
+    }
+
+    #[test]
+    fn test_possible_bags() {
+        let game = Game::parse_game("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green");
+        assert!(game.possible_bags(11).is_empty());
+        assert_eq!(game.possible_bags(12), vec![GameSample::new(4, 2, 6)]);
+        let bags = game.possible_bags(13);
+        assert_eq!(bags.len(), 3);
+        assert!(bags.iter().all(|bag| game.is_valid(bag) && bag.num_cubes() == 13));
+    }
 }
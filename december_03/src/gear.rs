@@ -0,0 +1,154 @@
+use crate::parser::{Number, GEAR_PATTERN};
+use crate::schematic::Schematic;
+use clap::ValueEnum;
+use regex::Regex;
+use std::ops::RangeInclusive;
+
+/// How the numbers adjacent to a gear are combined into its ratio.
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Aggregation {
+    Product,
+    Sum,
+    Max,
+}
+
+impl Aggregation {
+    pub fn aggregate(&self, numbers: &[&Number]) -> u32 {
+        let values = numbers.iter().map(|number| number.number());
+        match self {
+            Aggregation::Product => values.product(),
+            Aggregation::Sum => values.sum(),
+            Aggregation::Max => values.max().unwrap_or(0),
+        }
+    }
+}
+
+/// Parses the required neighbour count, either a single number `2` or a range such as
+/// `1..3`, `1..=3` or `2..`.
+pub fn parse_neighbours(value: &str) -> Result<RangeInclusive<usize>, String> {
+    let parse = |bound: &str| {
+        bound
+            .trim()
+            .parse::<usize>()
+            .map_err(|e| format!("invalid neighbour count '{}': {}", bound, e))
+    };
+    let range = match value.split_once("..") {
+        None => parse(value).map(|count| count..=count),
+        Some((start, "")) => Ok(parse(start)?..=usize::MAX),
+        Some((start, end)) => match end.strip_prefix('=') {
+            Some(end) => Ok(parse(start)?..=parse(end)?),
+            None => match parse(end)?.checked_sub(1) {
+                Some(end) => Ok(parse(start)?..=end),
+                None => Err(format!("empty neighbour range '{}'", value)),
+            },
+        },
+    }?;
+    if range.is_empty() {
+        return Err(format!("empty neighbour range '{}'", value));
+    }
+    Ok(range)
+}
+
+/// Rule deciding which symbols are gears and how their ratio is computed.
+pub struct GearRule {
+    pattern: Regex,
+    neighbours: RangeInclusive<usize>,
+    aggregation: Aggregation,
+}
+
+impl Default for GearRule {
+    fn default() -> Self {
+        GearRule {
+            pattern: GEAR_PATTERN.clone(),
+            neighbours: 2..=2,
+            aggregation: Aggregation::Product,
+        }
+    }
+}
+
+impl GearRule {
+    pub fn new(
+        symbols: &str,
+        neighbours: RangeInclusive<usize>,
+        aggregation: Aggregation,
+    ) -> Result<Self, String> {
+        if symbols.is_empty() {
+            return Err("no gear symbols given".to_string());
+        }
+        let pattern = Regex::new(&format!("([{}])", regex::escape(symbols)))
+            .map_err(|e| format!("invalid gear symbols '{}': {}", symbols, e))?;
+        Ok(GearRule {
+            pattern,
+            neighbours,
+            aggregation,
+        })
+    }
+
+    /// Ratios of all gears of the schematic, in the order the gears appear.
+    pub fn gear_ratios(&self, schematic: &Schematic) -> Vec<u32> {
+        schematic
+            .symbols()
+            .iter()
+            .filter(|&symbol| self.pattern.is_match(symbol.symbol()))
+            .filter_map(|symbol| {
+                let close_numbers = schematic.numbers_adjacent_to(symbol);
+                if self.neighbours.contains(&close_numbers.len()) {
+                    Some(self.aggregation.aggregate(&close_numbers))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_SCHEMATIC: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    #[test]
+    fn test_parse_neighbours() {
+        assert_eq!(parse_neighbours("2"), Ok(2..=2));
+        assert_eq!(parse_neighbours("1..3"), Ok(1..=2));
+        assert_eq!(parse_neighbours("1..=3"), Ok(1..=3));
+        assert_eq!(parse_neighbours("2.."), Ok(2..=usize::MAX));
+        assert!(parse_neighbours("1..0").is_err());
+        assert!(parse_neighbours("3..=1").is_err());
+        assert!(parse_neighbours("3..3").is_err());
+        assert!(parse_neighbours("two").is_err());
+    }
+
+    #[test]
+    fn test_default_rule() {
+        let schematic = Schematic::from(TEST_SCHEMATIC);
+        assert_eq!(
+            GearRule::default().gear_ratios(&schematic),
+            vec![16345, 451490]
+        );
+    }
+
+    #[test]
+    fn test_custom_rule() {
+        let schematic = Schematic::from(TEST_SCHEMATIC);
+        let rule = GearRule::new("*#", 1..=2, Aggregation::Sum).unwrap();
+        assert_eq!(rule.gear_ratios(&schematic), vec![502, 633, 617, 1353]);
+        let rule = GearRule::new("$+", 1..=usize::MAX, Aggregation::Max).unwrap();
+        assert_eq!(rule.gear_ratios(&schematic), vec![592, 664]);
+    }
+
+    #[test]
+    fn test_empty_gear_symbols() {
+        assert!(GearRule::new("", 2..=2, Aggregation::Product).is_err());
+    }
+}
@@ -0,0 +1,178 @@
+use crate::parser::{Number, Symbol};
+use crate::schematic::Schematic;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, PartialEq)]
+pub enum DiagnosticKind {
+    /// Number not adjacent to any symbol, so it is not a part number.
+    IsolatedNumber(u32),
+    /// Symbol without any adjacent number.
+    IsolatedSymbol(String),
+    /// Number adjacent to several symbols, which risks counting it more than once.
+    SharedNumber(u32, usize),
+    /// Symbol outside of ASCII, most likely a corrupted input.
+    NonAsciiSymbol(String),
+}
+
+/// Problem found in a schematic, located by its 1-based line and column.
+#[derive(Debug, PartialEq)]
+pub struct Diagnostic {
+    line: usize,
+    column: usize,
+    kind: DiagnosticKind,
+}
+
+impl Diagnostic {
+    fn for_number(schematic: &Schematic, number: &Number, kind: DiagnosticKind) -> Self {
+        Diagnostic {
+            line: number.line_no() + 1,
+            column: schematic.column(number.line_no(), number.start()),
+            kind,
+        }
+    }
+
+    fn for_symbol(schematic: &Schematic, symbol: &Symbol, kind: DiagnosticKind) -> Self {
+        Diagnostic {
+            line: symbol.line_no() + 1,
+            column: schematic.column(symbol.line_no(), symbol.position()),
+            kind,
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}: ", self.line, self.column)?;
+        match &self.kind {
+            DiagnosticKind::IsolatedNumber(number) => {
+                write!(f, "number {} is not adjacent to any symbol", number)
+            }
+            DiagnosticKind::IsolatedSymbol(symbol) => {
+                write!(f, "symbol '{}' is not adjacent to any number", symbol)
+            }
+            DiagnosticKind::SharedNumber(number, num_symbols) => {
+                write!(
+                    f,
+                    "number {} is adjacent to {} symbols",
+                    number, num_symbols
+                )
+            }
+            DiagnosticKind::NonAsciiSymbol(symbol) => {
+                write!(f, "symbol '{}' is not an ASCII character", symbol)
+            }
+        }
+    }
+}
+
+/// All diagnostics of the schematic ordered by their location.
+pub fn lint(schematic: &Schematic) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    for number in schematic.numbers() {
+        match schematic.symbols_adjacent_to(number).len() {
+            0 => diagnostics.push(Diagnostic::for_number(
+                schematic,
+                number,
+                DiagnosticKind::IsolatedNumber(number.number()),
+            )),
+            1 => {}
+            num_symbols => diagnostics.push(Diagnostic::for_number(
+                schematic,
+                number,
+                DiagnosticKind::SharedNumber(number.number(), num_symbols),
+            )),
+        }
+    }
+    for symbol in schematic.symbols() {
+        if !symbol.symbol().is_ascii() {
+            diagnostics.push(Diagnostic::for_symbol(
+                schematic,
+                symbol,
+                DiagnosticKind::NonAsciiSymbol(symbol.symbol().to_owned()),
+            ));
+        }
+        if schematic.numbers_adjacent_to(symbol).is_empty() {
+            diagnostics.push(Diagnostic::for_symbol(
+                schematic,
+                symbol,
+                DiagnosticKind::IsolatedSymbol(symbol.symbol().to_owned()),
+            ));
+        }
+    }
+    diagnostics.sort_by_key(|diagnostic| (diagnostic.line, diagnostic.column));
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lint() {
+        let schematic = Schematic::from("467..114..\n...*......\n..35*.§...\n......#...");
+        let diagnostics = lint(&schematic);
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic {
+                    line: 1,
+                    column: 6,
+                    kind: DiagnosticKind::IsolatedNumber(114)
+                },
+                Diagnostic {
+                    line: 3,
+                    column: 3,
+                    kind: DiagnosticKind::SharedNumber(35, 2)
+                },
+                Diagnostic {
+                    line: 3,
+                    column: 7,
+                    kind: DiagnosticKind::NonAsciiSymbol("§".to_owned())
+                },
+                Diagnostic {
+                    line: 3,
+                    column: 7,
+                    kind: DiagnosticKind::IsolatedSymbol("§".to_owned())
+                },
+                Diagnostic {
+                    line: 4,
+                    column: 7,
+                    kind: DiagnosticKind::IsolatedSymbol("#".to_owned())
+                },
+            ]
+        );
+        assert_eq!(
+            diagnostics[1].to_string(),
+            "3:3: number 35 is adjacent to 2 symbols"
+        );
+    }
+
+    #[test]
+    fn test_lint_columns_after_non_ascii() {
+        let schematic = Schematic::from("§...12\n......\n..#...");
+        assert_eq!(
+            lint(&schematic),
+            vec![
+                Diagnostic {
+                    line: 1,
+                    column: 1,
+                    kind: DiagnosticKind::NonAsciiSymbol("§".to_owned())
+                },
+                Diagnostic {
+                    line: 1,
+                    column: 1,
+                    kind: DiagnosticKind::IsolatedSymbol("§".to_owned())
+                },
+                Diagnostic {
+                    line: 1,
+                    column: 5,
+                    kind: DiagnosticKind::IsolatedNumber(12)
+                },
+                Diagnostic {
+                    line: 3,
+                    column: 3,
+                    kind: DiagnosticKind::IsolatedSymbol("#".to_owned())
+                },
+            ]
+        );
+    }
+}
@@ -1,14 +1,22 @@
+mod gear;
+mod lint;
 mod parser;
+mod schematic;
 
-use crate::parser::{parse_line, Number, Symbol, GEAR_PATTERN, NUMBER_PATTERN, SYMBOL_PATTERN};
+use crate::gear::{parse_neighbours, Aggregation, GearRule};
+use crate::lint::lint;
+use crate::schematic::Schematic;
 use clap::{Parser, ValueEnum};
 use std::fs::read_to_string;
+use std::ops::RangeInclusive;
 use std::time::Instant;
 
 #[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
 enum Tasks {
     Task1,
     Task2,
+    /// Report suspicious numbers and symbols of the schematic
+    Lint,
 }
 
 #[derive(Debug, Parser)]
@@ -18,60 +26,65 @@ struct Cli {
     /// Number of lines to read
     #[structopt(short = 't')]
     task: Tasks,
+    /// Symbols treated as gears by task 2
+    #[arg(long, default_value = "*")]
+    gear_symbols: String,
+    /// Number of adjacent numbers a gear needs, e.g. `2`, `1..=3` or `2..`
+    #[arg(long, value_parser = parse_neighbours, default_value = "2")]
+    neighbours: RangeInclusive<usize>,
+    /// How the numbers adjacent to a gear are combined
+    #[arg(long, value_enum, default_value_t = Aggregation::Product)]
+    aggregation: Aggregation,
 }
 
 fn task_1(input_file: &str) {
-    let mut numbers: Vec<Number> = Vec::new();
-    let mut symbols: Vec<Symbol> = Vec::new();
-    for (line_no, line) in read_to_string(input_file).unwrap().lines().enumerate() {
-        numbers.append(&mut parse_line(line, line_no, &NUMBER_PATTERN));
-        symbols.append(&mut parse_line(line, line_no, &SYMBOL_PATTERN));
-    }
+    let schematic = Schematic::from(read_to_string(input_file).unwrap().as_str());
 
-    let numbers_close_to_symbols = numbers
+    let numbers_close_to_symbols = schematic
+        .numbers()
         .iter()
-        .filter(|&number| {
-            symbols
-                .iter()
-                .any(|s| number.is_close_to(s.line_no(), s.position()))
-        })
+        .filter(|&number| !schematic.symbols_adjacent_to(number).is_empty())
         .map(|number| number.number())
         .collect::<Vec<u32>>();
     println!("{}", numbers_close_to_symbols.iter().sum::<u32>());
 }
 
-fn task_2(input_file: &str) {
-    let mut numbers: Vec<Number> = Vec::new();
-    let mut symbols: Vec<Symbol> = Vec::new();
-    for (line_no, line) in read_to_string(input_file).unwrap().lines().enumerate() {
-        numbers.append(&mut parse_line(line, line_no, &NUMBER_PATTERN));
-        symbols.append(&mut parse_line(line, line_no, &GEAR_PATTERN));
-    }
+fn task_2(input_file: &str, rule: &GearRule) {
+    let schematic = Schematic::from(read_to_string(input_file).unwrap().as_str());
 
-    let close_numbers = symbols
-        .iter()
-        .filter_map(|symbol| {
-            let close_numbers = numbers
-                .iter()
-                .filter(|&n| n.is_close_to(symbol.line_no(), symbol.position()))
-                .collect::<Vec<&Number>>();
-            return if close_numbers.len() == 2 {
-                Some(close_numbers.iter().map(|&n| n.number()).product())
-            } else {
-                None
-            };
-        })
-        .collect::<Vec<u32>>();
+    let close_numbers = rule.gear_ratios(&schematic);
     println!("{:?}", close_numbers.iter().sum::<u32>());
 }
 
+fn task_lint(input_file: &str) {
+    let schematic = Schematic::from(read_to_string(input_file).unwrap().as_str());
+
+    for diagnostic in lint(&schematic) {
+        println!("{}", diagnostic);
+    }
+}
+
 fn main() {
     let args = Cli::parse();
 
     let start = Instant::now();
     match args.task {
         Tasks::Task1 => task_1(&args.input_file),
-        Tasks::Task2 => task_2(&args.input_file),
+        Tasks::Task2 => {
+            let rule = match GearRule::new(
+                &args.gear_symbols,
+                args.neighbours.clone(),
+                args.aggregation,
+            ) {
+                Ok(rule) => rule,
+                Err(error) => {
+                    eprintln!("{}", error);
+                    std::process::exit(1);
+                }
+            };
+            task_2(&args.input_file, &rule)
+        }
+        Tasks::Lint => task_lint(&args.input_file),
     }
     println!("The task took {}ms to complete", start.elapsed().as_nanos() as f32/1_000_000.0);
 }
@@ -29,9 +29,9 @@ impl From<(Match<'_>, usize)> for Number {
 
 impl Number {
     pub fn is_close_to(&self, line: usize, position: usize) -> bool {
-        let pos_start = if self.start > 1 { self.start - 1 } else { 0 };
+        let pos_start = self.start.saturating_sub(1);
         let pos_end = self.end + 1;
-        let line_start = if self.line_no > 1 { self.line_no - 1 } else { 0 };
+        let line_start = self.line_no.saturating_sub(1);
         let line_end = self.line_no + 1;
         (pos_start <= position) && (position <= pos_end) && (line_start <= line) && (line <= line_end)
     }
@@ -39,6 +39,18 @@ impl Number {
     pub fn number(&self) -> u32 {
         self.number
     }
+
+    pub fn line_no(&self) -> usize {
+        self.line_no
+    }
+
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    pub fn end(&self) -> usize {
+        self.end
+    }
 }
 
 #[derive(PartialEq, Debug)]
@@ -60,6 +72,9 @@ impl From<(Match<'_>, usize)> for Symbol {
 }
 
 impl Symbol {
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
     pub fn line_no(&self) -> usize {
         self.line_no
     }
@@ -172,7 +187,7 @@ mod tests {
 
         for row in 0..10 {
             for col in 0..10 {
-                let exp_is_close = (3 <= row) && (row <= 5) && (2 <= col) && (col <= 6);
+                let exp_is_close = (3..=5).contains(&row) && (2..=6).contains(&col);
                 assert_eq!(
                     number.is_close_to(row, col),
                     exp_is_close,
@@ -195,7 +210,7 @@ mod tests {
 
         for row in 0..10 {
             for col in 0..10 {
-                let exp_is_close = (0 <= row) && (row <= 2) && (0 <= col) && (col <= 4);
+                let exp_is_close = row <= 2 && col <= 4;
                 assert_eq!(
                     number.is_close_to(row, col),
                     exp_is_close,
@@ -218,7 +233,7 @@ mod tests {
 
         for row in 0..10 {
             for col in 0..10 {
-                let exp_is_close = (0 <= row) && (row <= 1) && (0 <= col) && (col <= 3);
+                let exp_is_close = row <= 1 && col <= 3;
                 assert_eq!(
                     number.is_close_to(row, col),
                     exp_is_close,
@@ -0,0 +1,137 @@
+use crate::parser::{parse_line, Number, Symbol, NUMBER_PATTERN, SYMBOL_PATTERN};
+
+/// Engine schematic with symbols placed into a grid and numbers grouped by line, so that
+/// adjacency is resolved by looking at the neighbouring cells only.
+pub struct Schematic {
+    numbers: Vec<Number>,
+    symbols: Vec<Symbol>,
+    /// Index of the symbol starting at each position of each line.
+    symbol_grid: Vec<Vec<Option<usize>>>,
+    /// Indices of the numbers of each line, ordered by position.
+    number_lines: Vec<Vec<usize>>,
+    lines: Vec<String>,
+}
+
+impl From<&str> for Schematic {
+    fn from(input: &str) -> Self {
+        let mut numbers: Vec<Number> = Vec::new();
+        let mut symbols: Vec<Symbol> = Vec::new();
+        let mut symbol_grid: Vec<Vec<Option<usize>>> = Vec::new();
+        let mut number_lines: Vec<Vec<usize>> = Vec::new();
+        for (line_no, line) in input.lines().enumerate() {
+            let mut symbol_row = vec![None; line.len()];
+            for symbol in parse_line::<Symbol>(line, line_no, &SYMBOL_PATTERN) {
+                symbol_row[symbol.position()] = Some(symbols.len());
+                symbols.push(symbol);
+            }
+            symbol_grid.push(symbol_row);
+
+            let line_numbers = parse_line::<Number>(line, line_no, &NUMBER_PATTERN);
+            number_lines.push((numbers.len()..numbers.len() + line_numbers.len()).collect());
+            numbers.extend(line_numbers);
+        }
+        Schematic {
+            numbers,
+            symbols,
+            symbol_grid,
+            number_lines,
+            lines: input.lines().map(|line| line.to_owned()).collect(),
+        }
+    }
+}
+
+impl Schematic {
+    pub fn numbers(&self) -> &[Number] {
+        &self.numbers
+    }
+
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    /// 1-based column of the character at the byte position of the line.
+    pub fn column(&self, line_no: usize, position: usize) -> usize {
+        self.lines[line_no][..position].chars().count() + 1
+    }
+
+    pub fn numbers_adjacent_to(&self, symbol: &Symbol) -> Vec<&Number> {
+        let (line_no, position) = (symbol.line_no(), symbol.position());
+        (line_no.saturating_sub(1)..=line_no + 1)
+            .filter_map(|line| self.number_lines.get(line))
+            .flat_map(|line_numbers| {
+                let first = line_numbers.partition_point(|&i| self.numbers[i].end() + 1 < position);
+                line_numbers[first..]
+                    .iter()
+                    .map(|&i| &self.numbers[i])
+                    .take_while(|number| number.is_close_to(line_no, position))
+            })
+            .collect()
+    }
+
+    pub fn symbols_adjacent_to(&self, number: &Number) -> Vec<&Symbol> {
+        (number.line_no().saturating_sub(1)..=number.line_no() + 1)
+            .filter_map(|line| self.symbol_grid.get(line))
+            .flat_map(|symbol_row| {
+                (number.start().saturating_sub(1)..=number.end() + 1)
+                    .filter_map(|position| *symbol_row.get(position)?)
+            })
+            .map(|i| &self.symbols[i])
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_SCHEMATIC: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    #[test]
+    fn test_symbols_adjacent_to() {
+        let schematic = Schematic::from(TEST_SCHEMATIC);
+        let adjacent = schematic
+            .numbers()
+            .iter()
+            .filter(|number| !schematic.symbols_adjacent_to(number).is_empty())
+            .map(|number| number.number())
+            .collect::<Vec<u32>>();
+        assert_eq!(adjacent, vec![467, 35, 633, 617, 592, 755, 664, 598]);
+        assert_eq!(adjacent.iter().sum::<u32>(), 4361);
+    }
+
+    #[test]
+    fn test_numbers_adjacent_to() {
+        let schematic = Schematic::from(TEST_SCHEMATIC);
+        let adjacent = schematic
+            .symbols()
+            .iter()
+            .map(|symbol| {
+                schematic
+                    .numbers_adjacent_to(symbol)
+                    .iter()
+                    .map(|number| number.number())
+                    .collect::<Vec<u32>>()
+            })
+            .collect::<Vec<Vec<u32>>>();
+        assert_eq!(
+            adjacent,
+            vec![
+                vec![467, 35],
+                vec![633],
+                vec![617],
+                vec![592],
+                vec![664],
+                vec![755, 598],
+            ]
+        );
+    }
+}
@@ -0,0 +1,338 @@
+use clap::ValueEnum;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Debug, PartialEq)]
+pub enum ParseCardError {
+    MissingSeparator(char),
+    InvalidId(String),
+    InvalidNumber(String),
+    /// Number too large to fit into a [`NumberSet`].
+    NumberOutOfRange(u32),
+}
+
+impl Display for ParseCardError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseCardError::MissingSeparator(separator) => {
+                write!(f, "missing separator '{}'", separator)
+            }
+            ParseCardError::InvalidId(id) => write!(f, "invalid card id '{}'", id),
+            ParseCardError::InvalidNumber(number) => write!(f, "invalid number '{}'", number),
+            ParseCardError::NumberOutOfRange(number) => {
+                write!(f, "number {} is larger than {}", number, NumberSet::MAX)
+            }
+        }
+    }
+}
+
+/// Set of small numbers stored as a bitset.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct NumberSet(u128);
+
+impl NumberSet {
+    pub const MAX: u32 = u128::BITS - 1;
+
+    pub fn insert(&mut self, number: u32) {
+        self.0 |= 1 << number;
+    }
+
+    pub fn intersection(&self, other: &NumberSet) -> NumberSet {
+        NumberSet(self.0 & other.0)
+    }
+
+    pub fn len(&self) -> u32 {
+        self.0.count_ones()
+    }
+}
+
+impl<'a> FromIterator<&'a u32> for NumberSet {
+    fn from_iter<T: IntoIterator<Item = &'a u32>>(iter: T) -> Self {
+        let mut set = NumberSet::default();
+        iter.into_iter().for_each(|&number| set.insert(number));
+        set
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Card {
+    id: u32,
+    winning: Vec<u32>,
+    having: Vec<u32>,
+}
+
+fn parse_numbers(numbers: &str) -> Result<Vec<u32>, ParseCardError> {
+    numbers
+        .split_whitespace()
+        .map(|number| {
+            let number = number
+                .parse::<u32>()
+                .map_err(|_| ParseCardError::InvalidNumber(number.to_owned()))?;
+            if number > NumberSet::MAX {
+                return Err(ParseCardError::NumberOutOfRange(number));
+            }
+            Ok(number)
+        })
+        .collect()
+}
+
+impl FromStr for Card {
+    type Err = ParseCardError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let (card_name, numbers) = line
+            .split_once(':')
+            .ok_or(ParseCardError::MissingSeparator(':'))?;
+        let id_str = card_name.trim().trim_start_matches("Card").trim();
+        let id = id_str
+            .parse()
+            .map_err(|_| ParseCardError::InvalidId(id_str.to_owned()))?;
+        let (winning_str, having_str) = numbers
+            .split_once('|')
+            .ok_or(ParseCardError::MissingSeparator('|'))?;
+        Ok(Card {
+            id,
+            winning: parse_numbers(winning_str)?,
+            having: parse_numbers(having_str)?,
+        })
+    }
+}
+
+impl Card {
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Number of distinct winning numbers the card has.
+    pub fn matches(&self) -> u32 {
+        let winning = self.winning.iter().collect::<NumberSet>();
+        let having = self.having.iter().collect::<NumberSet>();
+        winning.intersection(&having).len()
+    }
+
+    /// Points of the card, doubling with each match. A [`NumberSet`] holds at most 128 numbers,
+    /// so they always fit into a `u128`.
+    pub fn points(&self) -> u128 {
+        match self.matches() {
+            0 => 0,
+            num => 1 << (num - 1),
+        }
+    }
+}
+
+/// What to do with copies won of cards past the end of the table.
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Overflow {
+    /// Silently drop the copies which do not exist
+    Clamp,
+    /// Fail with a [`CopyError`]
+    Error,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum CopyError {
+    /// Card `id` wins copies of `matches` following cards while only `remaining` follow it.
+    Overflow {
+        id: u32,
+        matches: u32,
+        remaining: usize,
+    },
+    /// Number of copies of card `id` does not fit into a `u64`.
+    TooManyCopies { id: u32 },
+}
+
+impl Display for CopyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CopyError::Overflow {
+                id,
+                matches,
+                remaining,
+            } => write!(
+                f,
+                "card {} wins copies of {} cards but only {} cards follow it",
+                id, matches, remaining
+            ),
+            CopyError::TooManyCopies { id } => {
+                write!(f, "card {} has more than {} copies", id, u64::MAX)
+            }
+        }
+    }
+}
+
+/// Outcome of the copy cascade for a single card.
+#[derive(Debug, PartialEq)]
+pub struct CardTrace {
+    pub id: u32,
+    pub matches: u32,
+    pub copies: u64,
+    /// Ids of the earlier cards which won copies of this card.
+    pub contributors: Vec<u32>,
+}
+
+/// Runs the copy cascade and records, for each card, how many copies are owned and where they
+/// came from.
+pub fn trace_copies(cards: &[Card], overflow: Overflow) -> Result<Vec<CardTrace>, CopyError> {
+    let mut traces = cards
+        .iter()
+        .map(|card| CardTrace {
+            id: card.id(),
+            matches: card.matches(),
+            copies: 1,
+            contributors: Vec::new(),
+        })
+        .collect::<Vec<CardTrace>>();
+    for i in 0..traces.len() {
+        let (id, matches, increment) = (traces[i].id, traces[i].matches, traces[i].copies);
+        let remaining = traces.len() - i - 1;
+        if matches as usize > remaining && overflow == Overflow::Error {
+            return Err(CopyError::Overflow {
+                id,
+                matches,
+                remaining,
+            });
+        }
+        let end = i + 1 + (matches as usize).min(remaining);
+        for trace in &mut traces[(i + 1)..end] {
+            trace.copies = trace
+                .copies
+                .checked_add(increment)
+                .ok_or(CopyError::TooManyCopies { id: trace.id })?;
+            trace.contributors.push(id);
+        }
+    }
+    Ok(traces)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_card() {
+        let card: Card = "Card 12:  1 48 | 01  6 48".parse().unwrap();
+        assert_eq!(
+            card,
+            Card {
+                id: 12,
+                winning: vec![1, 48],
+                having: vec![1, 6, 48],
+            }
+        );
+        assert_eq!(card.matches(), 2);
+        assert_eq!(card.points(), 2);
+    }
+
+    #[test]
+    fn test_points_of_all_numbers() {
+        let numbers = (0..=NumberSet::MAX)
+            .map(|number| number.to_string())
+            .collect::<Vec<String>>()
+            .join(" ");
+        let card: Card = format!("Card 1: {} | {}", numbers, numbers)
+            .parse()
+            .unwrap();
+        assert_eq!(card.matches(), 128);
+        assert_eq!(card.points(), 1 << 127);
+    }
+
+    #[test]
+    fn test_parse_card_errors() {
+        assert_eq!(
+            "Card 1 41 48 | 83 86".parse::<Card>(),
+            Err(ParseCardError::MissingSeparator(':'))
+        );
+        assert_eq!(
+            "Card 1: 41 48 83 86".parse::<Card>(),
+            Err(ParseCardError::MissingSeparator('|'))
+        );
+        assert_eq!(
+            "Card x: 41 48 | 83 86".parse::<Card>(),
+            Err(ParseCardError::InvalidId("x".to_owned()))
+        );
+        assert_eq!(
+            "Card 1: 41 a8 | 83 86".parse::<Card>(),
+            Err(ParseCardError::InvalidNumber("a8".to_owned()))
+        );
+        assert_eq!(
+            "Card 1: 41 48 | 83 300".parse::<Card>(),
+            Err(ParseCardError::NumberOutOfRange(300))
+        );
+    }
+
+    fn test_cards() -> Vec<Card> {
+        [
+            "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53",
+            "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19",
+            "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1",
+            "Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83",
+            "Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36",
+            "Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11",
+        ]
+        .iter()
+        .map(|line| line.parse().unwrap())
+        .collect()
+    }
+
+    fn copies(cards: &[Card], overflow: Overflow) -> Result<Vec<u64>, CopyError> {
+        Ok(trace_copies(cards, overflow)?
+            .iter()
+            .map(|trace| trace.copies)
+            .collect())
+    }
+
+    #[test]
+    fn test_copies() {
+        assert_eq!(
+            copies(&test_cards(), Overflow::Error),
+            Ok(vec![1, 2, 4, 8, 14, 1])
+        );
+    }
+
+    #[test]
+    fn test_copies_overflow() {
+        let cards = &test_cards()[..3];
+        assert_eq!(
+            copies(cards, Overflow::Error),
+            Err(CopyError::Overflow {
+                id: 1,
+                matches: 4,
+                remaining: 2
+            })
+        );
+        assert_eq!(copies(cards, Overflow::Clamp), Ok(vec![1, 2, 4]));
+    }
+
+    #[test]
+    fn test_too_many_copies() {
+        // Every card wins a copy of all the following ones, doubling the copies of each card.
+        let cards = (1..=70)
+            .map(|id| {
+                let numbers = (0..(70 - id)).map(|number| number.to_string());
+                let numbers = numbers.collect::<Vec<String>>().join(" ");
+                format!("Card {}: {} | {}", id, numbers, numbers)
+                    .parse()
+                    .unwrap()
+            })
+            .collect::<Vec<Card>>();
+        assert_eq!(copies(&cards[..64], Overflow::Clamp).unwrap()[63], 1 << 63);
+        assert_eq!(
+            copies(&cards, Overflow::Clamp),
+            Err(CopyError::TooManyCopies { id: 65 })
+        );
+    }
+
+    #[test]
+    fn test_trace_copies() {
+        let traces = trace_copies(&test_cards(), Overflow::Error).unwrap();
+        assert_eq!(
+            traces[3],
+            CardTrace {
+                id: 4,
+                matches: 1,
+                copies: 8,
+                contributors: vec![1, 2, 3],
+            }
+        );
+    }
+}
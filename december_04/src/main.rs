@@ -1,5 +1,8 @@
-use std::collections::HashSet;
+mod card;
+mod trace;
 
+use crate::card::{trace_copies, Card, CopyError, Overflow, ParseCardError};
+use crate::trace::TraceFormat;
 use clap::{Parser, ValueEnum};
 use std::fs::read_to_string;
 use std::time::Instant;
@@ -17,55 +20,67 @@ struct Cli {
     /// Number of lines to read
     #[structopt(short = 't')]
     task: Tasks,
+    /// What to do when a card wins copies of cards past the end of the table
+    #[arg(long, value_enum, default_value_t = Overflow::Error)]
+    overflow: Overflow,
+    /// Print the copies cascade of task 2 card by card
+    #[arg(long, value_enum)]
+    trace: Option<TraceFormat>,
 }
 
-fn compute_numbers(line: &str) -> u32 {
-    let (_, numbers) = line.split_once(":").unwrap();
-    let (having_numbers_str, winning_numbers_str) = numbers.split_once("|").unwrap();
-    let having_numbers = having_numbers_str
-        .split_whitespace()
-        .collect::<HashSet<&str>>();
-    let winning_numbers = winning_numbers_str
-        .split_whitespace()
-        .collect::<HashSet<&str>>();
-    having_numbers.intersection(&winning_numbers).count() as u32
-}
-
-fn task_1(input_file: &str) -> u32 {
+fn parse_cards(input_file: &str) -> Result<Vec<Card>, ParseCardError> {
     read_to_string(input_file)
         .unwrap()
         .lines()
-        .map(compute_numbers)
-        .map(|num| match num {
-            0 => 0,
-            1.. => 1 << (num - 1),
-        })
-        .sum()
+        .map(|line| line.parse::<Card>())
+        .collect()
 }
 
-fn task_2(input_file: &str) -> u32 {
-    let numbers = read_to_string(input_file)
-        .unwrap()
-        .lines()
-        .map(compute_numbers)
-        .collect::<Vec<u32>>();
-    let mut won_cards = vec![1; numbers.len()];
-    for (i, num) in numbers.iter().enumerate() {
-        let increment = won_cards[i];
-        for v in &mut won_cards[(i + 1usize)..(i + (*num as usize) + 1usize)] {
-            *v += increment.clone();
-        }
+/// Sum of the points of all cards, or `None` if it does not fit into `u128`.
+fn task_1(cards: &[Card]) -> Option<u128> {
+    cards
+        .iter()
+        .try_fold(0u128, |total, card| total.checked_add(card.points()))
+}
+
+fn task_2(
+    cards: &[Card],
+    overflow: Overflow,
+    trace: Option<TraceFormat>,
+) -> Result<u128, CopyError> {
+    let traces = trace_copies(cards, overflow)?;
+    if let Some(trace_format) = trace {
+        println!("{}", trace_format.format(&traces));
     }
-    won_cards.iter().sum()
+    Ok(traces.iter().map(|trace| u128::from(trace.copies)).sum())
 }
 
 fn main() {
     let args = Cli::parse();
 
     let start = Instant::now();
+    let cards = match parse_cards(&args.input_file) {
+        Ok(cards) => cards,
+        Err(error) => {
+            eprintln!("invalid card: {}", error);
+            std::process::exit(1);
+        }
+    };
     let result = match args.task {
-        Tasks::Task1 => task_1(&args.input_file),
-        Tasks::Task2 => task_2(&args.input_file),
+        Tasks::Task1 => match task_1(&cards) {
+            Some(result) => result,
+            None => {
+                eprintln!("total points exceed {}", u128::MAX);
+                std::process::exit(1);
+            }
+        },
+        Tasks::Task2 => match task_2(&cards, args.overflow, args.trace) {
+            Ok(result) => result,
+            Err(error) => {
+                eprintln!("{}", error);
+                std::process::exit(1);
+            }
+        },
     };
     println!(
         "The task took {}ms to complete",
@@ -77,43 +92,64 @@ fn main() {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::card::NumberSet;
+
+    fn matches(line: &str) -> u32 {
+        line.parse::<Card>().unwrap().matches()
+    }
 
     #[test]
     fn process_line() {
         assert_eq!(
-            compute_numbers("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53"),
+            matches("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53"),
             4
         );
         assert_eq!(
-            compute_numbers("Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19"),
+            matches("Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19"),
             2
         );
         assert_eq!(
-            compute_numbers("Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1"),
+            matches("Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1"),
             2
         );
         assert_eq!(
-            compute_numbers("Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83"),
+            matches("Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83"),
             1
         );
         assert_eq!(
-            compute_numbers("Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36"),
+            matches("Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36"),
             0
         );
         assert_eq!(
-            compute_numbers("Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"),
+            matches("Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"),
             0
         );
     }
 
     #[test]
     fn test_task_1() {
-        assert_eq!(task_1("./inputs/input_test.txt"), 13);
+        let cards = parse_cards("./inputs/input_test.txt").unwrap();
+        assert_eq!(task_1(&cards), Some(13));
     }
 
     #[test]
-    fn test_task_2() {
-        assert_eq!(task_2("./inputs/input_test.txt"), 30);
+    fn test_task_1_overflow() {
+        let numbers = (0..=NumberSet::MAX)
+            .map(|number| number.to_string())
+            .collect::<Vec<String>>()
+            .join(" ");
+        let card = |id: u32| {
+            format!("Card {}: {} | {}", id, numbers, numbers)
+                .parse::<Card>()
+                .unwrap()
+        };
+        assert_eq!(task_1(&[card(1)]), Some(1 << 127));
+        assert_eq!(task_1(&[card(1), card(2)]), None);
     }
 
+    #[test]
+    fn test_task_2() {
+        let cards = parse_cards("./inputs/input_test.txt").unwrap();
+        assert_eq!(task_2(&cards, Overflow::Error, None), Ok(30));
+    }
 }
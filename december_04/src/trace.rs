@@ -0,0 +1,88 @@
+use crate::card::CardTrace;
+use clap::ValueEnum;
+
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TraceFormat {
+    Table,
+    Json,
+}
+
+impl TraceFormat {
+    pub fn format(&self, traces: &[CardTrace]) -> String {
+        match self {
+            TraceFormat::Table => format_table(traces),
+            TraceFormat::Json => format_json(traces),
+        }
+    }
+}
+
+fn join_ids(ids: &[u32], separator: &str) -> String {
+    ids.iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<String>>()
+        .join(separator)
+}
+
+fn format_table(traces: &[CardTrace]) -> String {
+    let mut lines = vec![format!(
+        "{:>6} {:>8} {:>10}  contributors",
+        "card", "matches", "copies"
+    )];
+    lines.extend(traces.iter().map(|trace| {
+        format!(
+            "{:>6} {:>8} {:>10}  {}",
+            trace.id,
+            trace.matches,
+            trace.copies,
+            join_ids(&trace.contributors, ", ")
+        )
+    }));
+    lines.join("\n")
+}
+
+fn format_json(traces: &[CardTrace]) -> String {
+    let cards = traces
+        .iter()
+        .map(|trace| {
+            format!(
+                "  {{\"card\": {}, \"matches\": {}, \"copies\": {}, \"contributors\": [{}]}}",
+                trace.id,
+                trace.matches,
+                trace.copies,
+                join_ids(&trace.contributors, ", ")
+            )
+        })
+        .collect::<Vec<String>>();
+    format!("[\n{}\n]", cards.join(",\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format() {
+        let traces = vec![
+            CardTrace {
+                id: 1,
+                matches: 2,
+                copies: 1,
+                contributors: vec![],
+            },
+            CardTrace {
+                id: 2,
+                matches: 0,
+                copies: 2,
+                contributors: vec![1],
+            },
+        ];
+        assert_eq!(
+            TraceFormat::Table.format(&traces),
+            "  card  matches     copies  contributors\n     1        2          1  \n     2        0          2  1"
+        );
+        assert_eq!(
+            TraceFormat::Json.format(&traces),
+            "[\n  {\"card\": 1, \"matches\": 2, \"copies\": 1, \"contributors\": []},\n  {\"card\": 2, \"matches\": 0, \"copies\": 2, \"contributors\": [1]}\n]"
+        );
+    }
+}
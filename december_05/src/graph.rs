@@ -0,0 +1,146 @@
+use crate::Mapping;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, PartialEq)]
+pub enum GraphError {
+    UnknownCategory(String),
+    NoPath(String, String),
+}
+
+impl Display for GraphError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GraphError::UnknownCategory(category) => write!(f, "unknown category '{}'", category),
+            GraphError::NoPath(source, target) => {
+                write!(f, "no mappings lead from '{}' to '{}'", source, target)
+            }
+        }
+    }
+}
+
+/// Categories of the almanac connected by the mappings between them.
+pub struct CategoryGraph<'a> {
+    edges: HashMap<&'a str, Vec<&'a Mapping>>,
+}
+
+impl<'a> CategoryGraph<'a> {
+    /// Builds the graph from mappings with `<source>-to-<target> map:` headers, mappings with
+    /// other headers are left out.
+    pub fn new(mappings: &'a [Mapping]) -> Self {
+        let mut edges: HashMap<&'a str, Vec<&'a Mapping>> = HashMap::new();
+        for mapping in mappings {
+            if let Some((source, target)) = mapping.categories() {
+                edges.entry(source).or_default().push(mapping);
+                edges.entry(target).or_default();
+            }
+        }
+        CategoryGraph { edges }
+    }
+
+    pub fn categories(&self) -> Vec<&'a str> {
+        let mut categories = self.edges.keys().copied().collect::<Vec<&str>>();
+        categories.sort();
+        categories
+    }
+
+    /// Shortest chain of mappings leading from `source` to `target`.
+    pub fn path(&self, source: &str, target: &str) -> Result<Vec<&'a Mapping>, GraphError> {
+        for category in [source, target] {
+            if !self.edges.contains_key(category) {
+                return Err(GraphError::UnknownCategory(category.to_owned()));
+            }
+        }
+        let mut previous: HashMap<&str, &'a Mapping> = HashMap::new();
+        let mut queue = VecDeque::from([source]);
+        while let Some(category) = queue.pop_front() {
+            if category == target {
+                let mut path = Vec::new();
+                let mut current = target;
+                while current != source {
+                    let mapping = previous[current];
+                    path.push(mapping);
+                    current = mapping.categories().unwrap().0;
+                }
+                path.reverse();
+                return Ok(path);
+            }
+            for &mapping in &self.edges[category] {
+                let next = mapping.categories().unwrap().1;
+                if next != source && !previous.contains_key(next) {
+                    previous.insert(next, mapping);
+                    queue.push_back(next);
+                }
+            }
+        }
+        Err(GraphError::NoPath(source.to_owned(), target.to_owned()))
+    }
+
+    /// Maps `value` of the `source` category onto the `target` category.
+    pub fn map(&self, value: i64, source: &str, target: &str) -> Result<i64, GraphError> {
+        Ok(self
+            .path(source, target)?
+            .iter()
+            .fold(value, |value, mapping| mapping.map(value)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_mappings() -> Vec<Mapping> {
+        vec![
+            Mapping::try_from("seed-to-soil map:\n50 98 2\n52 50 48").unwrap(),
+            Mapping::try_from("soil-to-fertilizer map:\n0 15 37\n37 52 2\n39 0 15").unwrap(),
+            Mapping::try_from("fertilizer-to-water map:\n49 53 8\n0 11 42\n42 0 7\n57 7 4")
+                .unwrap(),
+            Mapping::try_from("seed-to-water map:\n0 0 10").unwrap(),
+        ]
+    }
+
+    #[test]
+    fn test_categories() {
+        let mappings = test_mappings();
+        let graph = CategoryGraph::new(&mappings);
+        assert_eq!(
+            graph.categories(),
+            vec!["fertilizer", "seed", "soil", "water"]
+        );
+    }
+
+    #[test]
+    fn test_path() {
+        let mappings = test_mappings();
+        let graph = CategoryGraph::new(&mappings);
+        let ids = |path: Vec<&Mapping>| path.iter().map(|m| m.id.clone()).collect::<Vec<String>>();
+        assert_eq!(
+            graph.path("soil", "water").map(ids),
+            Ok(vec![
+                "soil-to-fertilizer map:".to_string(),
+                "fertilizer-to-water map:".to_string()
+            ])
+        );
+        assert_eq!(
+            graph.path("seed", "water").map(ids),
+            Ok(vec!["seed-to-water map:".to_string()])
+        );
+        assert_eq!(graph.path("soil", "soil").map(ids), Ok(vec![]));
+        assert_eq!(
+            graph.path("water", "seed").map(ids),
+            Err(GraphError::NoPath("water".to_string(), "seed".to_string()))
+        );
+        assert_eq!(
+            graph.path("seed", "humidity").map(ids),
+            Err(GraphError::UnknownCategory("humidity".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_map() {
+        let mappings = test_mappings();
+        let graph = CategoryGraph::new(&mappings);
+        assert_eq!(graph.map(14, "soil", "water"), Ok(49));
+        assert_eq!(graph.map(81, "soil", "water"), Ok(81));
+    }
+}
@@ -1,12 +1,26 @@
+mod graph;
+mod validation;
+
+use crate::graph::{CategoryGraph, GraphError};
+use crate::validation::validate;
 use clap::{Parser, ValueEnum};
-use rayon::prelude::*;
+use std::fmt::{Display, Formatter};
 use std::fs::read_to_string;
+use std::ops::Range;
 use std::time::Instant;
 
 #[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Tasks {
     Task1,
     Task2,
+    /// Print the seed-to-location mappings composed into a single table
+    Compose,
+    /// Find the seed, and its seed range, leading to a location
+    Reverse,
+    /// Map values from one category to another
+    Convert,
+    /// Report overlapping, empty and overflowing ranges of the mappings
+    Validate,
 }
 
 #[derive(Debug, Parser)]
@@ -16,6 +30,52 @@ pub struct Cli {
     /// Number of lines to read
     #[structopt(short = 't')]
     task: Tasks,
+    /// Look seeds up in the composed seed-to-location table in task 1
+    #[arg(long)]
+    composed: bool,
+    /// Location searched for by the reverse task, defaults to the lowest reachable location
+    #[arg(long)]
+    location: Option<i64>,
+    /// Category of the values converted by the convert task
+    #[arg(long, default_value = "seed")]
+    from: String,
+    /// Category the convert task maps onto
+    #[arg(long, default_value = "location")]
+    to: String,
+    /// Values converted by the convert task, defaults to the seeds of the almanac
+    #[arg(long, num_args = 1..)]
+    values: Vec<i64>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ParseMappingError {
+    /// Line of a mapping with less than three values.
+    MissingValue,
+    /// Value which is not an integer fitting into `i64`.
+    InvalidValue(String),
+    /// First value following the three values of a mapping line.
+    ExtraValue(String),
+    /// Almanac without any seed, or whose seed ranges are all empty.
+    NoSeeds,
+}
+
+impl Display for ParseMappingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseMappingError::MissingValue => write!(f, "expected three values"),
+            ParseMappingError::InvalidValue(value) => write!(f, "invalid value '{}'", value),
+            ParseMappingError::ExtraValue(value) => {
+                write!(f, "unexpected value '{}' after three values", value)
+            }
+            ParseMappingError::NoSeeds => write!(f, "no seeds to map"),
+        }
+    }
+}
+
+fn parse_value(value: &str) -> Result<i64, ParseMappingError> {
+    value
+        .parse()
+        .map_err(|_| ParseMappingError::InvalidValue(value.to_owned()))
 }
 
 #[derive(Ord, PartialOrd, Eq, PartialEq)]
@@ -42,19 +102,49 @@ impl SingleMapping {
         self.src_range_start + self.range_len
     }
 
+    pub fn inverse(&self) -> SingleMapping {
+        SingleMapping::new(self.src_range_start, self.dest_range_start, self.range_len)
+    }
+
+    pub fn offset(&self) -> i64 {
+        self.dest_range_start - self.src_range_start
+    }
+
     pub fn map(&self, source: i64) -> i64 {
         source - self.src_range_start + self.dest_range_start
     }
+
+    /// Splits `range` into its overlap with the source range and the parts before and after it.
+    pub fn split(&self, range: &Range<i64>) -> (Option<Range<i64>>, Vec<Range<i64>>) {
+        let overlap_start = range.start.max(self.src_range_start());
+        let overlap_end = range.end.min(self.src_range_end());
+        if overlap_start >= overlap_end {
+            return (None, vec![range.clone()]);
+        }
+        let overlap = overlap_start..overlap_end;
+        let rest = [range.start..overlap_start, overlap_end..range.end]
+            .into_iter()
+            .filter(|r| !r.is_empty())
+            .collect();
+        (Some(overlap), rest)
+    }
 }
 
-impl From<&str> for SingleMapping {
-    fn from(value: &str) -> Self {
+impl TryFrom<&str> for SingleMapping {
+    type Error = ParseMappingError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
         let mut parts = value.split_whitespace();
-        SingleMapping::new(
-            parts.next().unwrap().parse::<i64>().unwrap().to_owned(),
-            parts.next().unwrap().parse::<i64>().unwrap().to_owned(),
-            parts.next().unwrap().parse::<i64>().unwrap().to_owned(),
-        )
+        let mut next = || {
+            parts
+                .next()
+                .map_or(Err(ParseMappingError::MissingValue), parse_value)
+        };
+        let mapping = SingleMapping::new(next()?, next()?, next()?);
+        match parts.next() {
+            Some(extra) => Err(ParseMappingError::ExtraValue(extra.to_owned())),
+            None => Ok(mapping),
+        }
     }
 }
 
@@ -63,18 +153,28 @@ struct Mapping {
     mappings: Vec<SingleMapping>,
 }
 
-impl From<&str> for Mapping {
-    fn from(chunk: &str) -> Self {
+impl TryFrom<&str> for Mapping {
+    type Error = ParseMappingError;
+
+    fn try_from(chunk: &str) -> Result<Self, Self::Error> {
         let mut lines = chunk.lines();
-        let id = lines.next().unwrap().to_owned();
-        let mut mappings = lines
-            .map(SingleMapping::from)
-            .collect::<Vec<SingleMapping>>();
-        Mapping { id, mappings }
+        let id = lines.next().unwrap_or_default().to_owned();
+        let mappings = lines
+            .map(SingleMapping::try_from)
+            .collect::<Result<Vec<SingleMapping>, ParseMappingError>>()?;
+        Ok(Mapping { id, mappings })
     }
 }
 
 impl Mapping {
+    /// Source and target category parsed from a `<source>-to-<target> map:` header.
+    pub fn categories(&self) -> Option<(&str, &str)> {
+        self.id
+            .trim()
+            .strip_suffix(" map:")
+            .and_then(|name| name.split_once("-to-"))
+    }
+
     pub fn map(&self, source: i64) -> i64 {
         let maybe_mapping = self
             .mappings
@@ -85,6 +185,140 @@ impl Mapping {
             Some(mapping) => mapping.map(source),
         }
     }
+
+    /// Mapping with source and destination ranges swapped. Unlike the original mapping, the
+    /// inverse does not account for values mapped onto themselves outside of the ranges, use
+    /// [`Mapping::preimage`] for exact reverse lookups.
+    pub fn inverse(&self) -> Mapping {
+        let id = match self.categories() {
+            Some((source, target)) => format!("{}-to-{} map:", target, source),
+            None => self.id.clone(),
+        };
+        Mapping {
+            id,
+            mappings: self.mappings.iter().map(SingleMapping::inverse).collect(),
+        }
+    }
+
+    /// All sources which the mapping maps onto `target`, in ascending order.
+    pub fn preimage(&self, target: i64) -> Vec<i64> {
+        let mut sources = self
+            .inverse()
+            .mappings
+            .iter()
+            .filter(|m| (m.src_range_start() <= target) && (target < m.src_range_end()))
+            .map(|m| m.map(target))
+            .chain([target])
+            .filter(|&source| self.map(source) == target)
+            .collect::<Vec<i64>>();
+        sources.sort();
+        sources.dedup();
+        sources
+    }
+
+    /// Same as [`Mapping::map`] for mappings with sorted and disjoint source ranges, such as
+    /// the ones produced by [`Mapping::compose`], but using a binary search.
+    pub fn lookup(&self, source: i64) -> i64 {
+        let index = self
+            .mappings
+            .partition_point(|m| m.src_range_end() <= source);
+        match self.mappings.get(index) {
+            Some(mapping) if mapping.src_range_start() <= source => mapping.map(source),
+            _ => source,
+        }
+    }
+
+    /// Splits `range` into disjoint non-empty parts, each paired with the offset the mapping
+    /// adds to its values. Parts not covered by any single mapping get an offset of zero.
+    fn split(&self, range: Range<i64>) -> Vec<(Range<i64>, i64)> {
+        let mut parts = Vec::new();
+        let mut unmapped = if range.is_empty() {
+            Vec::new()
+        } else {
+            vec![range]
+        };
+        for mapping in &self.mappings {
+            unmapped = unmapped
+                .iter()
+                .flat_map(|r| {
+                    let (overlap, rest) = mapping.split(r);
+                    parts.extend(overlap.map(|overlap| (overlap, mapping.offset())));
+                    rest
+                })
+                .collect();
+        }
+        parts.extend(unmapped.into_iter().map(|r| (r, 0)));
+        parts
+    }
+
+    /// Maps every value of `range`, returning the resulting values as a list of ranges.
+    pub fn map_range(&self, range: Range<i64>) -> Vec<Range<i64>> {
+        self.split(range)
+            .into_iter()
+            .map(|(r, offset)| (r.start + offset)..(r.end + offset))
+            .collect()
+    }
+
+    /// Composes the mappings, applied in the given order, into a single mapping with sorted
+    /// source ranges covering all non-negative values, including identity ranges for the gaps.
+    pub fn compose(mappings: &[Mapping]) -> Mapping {
+        let source = mappings.first().and_then(Mapping::categories);
+        let target = mappings.last().and_then(Mapping::categories);
+        let id = match (source, target) {
+            (Some((source, _)), Some((_, target))) => format!("{}-to-{} map:", source, target),
+            _ => "composed map:".to_owned(),
+        };
+
+        let mut parts: Vec<(Range<i64>, i64)> = vec![(0..i64::MAX, 0)];
+        for mapping in mappings {
+            parts = parts
+                .into_iter()
+                .flat_map(|(range, offset)| {
+                    // Values shifted past the bounds of i64 cannot be represented, so the
+                    // shifted range is clamped instead.
+                    let shifted =
+                        range.start.saturating_add(offset)..range.end.saturating_add(offset);
+                    mapping
+                        .split(shifted)
+                        .into_iter()
+                        .map(move |(r, o)| ((r.start - offset)..(r.end - offset), offset + o))
+                })
+                .collect();
+        }
+        parts.sort_by_key(|(range, _)| range.start);
+
+        let mut composed: Vec<SingleMapping> = Vec::new();
+        for (range, offset) in parts {
+            match composed.last_mut() {
+                Some(last) if last.offset() == offset && last.src_range_end() == range.start => {
+                    last.range_len += range.end - range.start
+                }
+                _ => composed.push(SingleMapping::new(
+                    range.start + offset,
+                    range.start,
+                    range.end - range.start,
+                )),
+            }
+        }
+        Mapping {
+            id,
+            mappings: composed,
+        }
+    }
+}
+
+impl Display for Mapping {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.id)?;
+        for mapping in &self.mappings {
+            write!(
+                f,
+                "\n{} {} {}",
+                mapping.dest_range_start, mapping.src_range_start, mapping.range_len
+            )?;
+        }
+        Ok(())
+    }
 }
 
 fn map(source: i64, mappings: &Vec<Mapping>) -> i64 {
@@ -95,45 +329,167 @@ fn map(source: i64, mappings: &Vec<Mapping>) -> i64 {
     target
 }
 
-fn task_1(input_file: &str) -> i64 {
-    let file_content = read_to_string(input_file).unwrap();
-    let mut chunks = file_content.split("\n\n");
-    let seeds_str = chunks.next().unwrap().split_once(":").unwrap().1;
+/// All sources which the chain of mappings maps onto `target`, in ascending order.
+fn reverse_map(target: i64, mappings: &[Mapping]) -> Vec<i64> {
+    let mut sources = vec![target];
+    for mapping in mappings.iter().rev() {
+        sources = sources
+            .iter()
+            .flat_map(|&source| mapping.preimage(source))
+            .collect();
+        sources.sort();
+        sources.dedup();
+    }
+    sources
+}
 
-    let mappings = chunks.map(Mapping::from).collect::<Vec<Mapping>>();
+/// Lowest seed leading to `location` together with the seed range containing it.
+fn find_seed(
+    location: i64,
+    seed_ranges: &[Range<i64>],
+    mappings: &[Mapping],
+) -> Option<(i64, Range<i64>)> {
+    reverse_map(location, mappings)
+        .into_iter()
+        .find_map(|seed| {
+            seed_ranges
+                .iter()
+                .find(|range| range.contains(&seed))
+                .map(|range| (seed, range.clone()))
+        })
+}
 
-    seeds_str
-        .split_whitespace()
-        .map(|seed_str| seed_str.parse().unwrap())
-        .map(|seed| map(seed, &mappings))
-        .min()
-        .unwrap()
+fn map_ranges(sources: Vec<Range<i64>>, mappings: &Vec<Mapping>) -> Vec<Range<i64>> {
+    let mut targets = sources;
+    for mapping in mappings {
+        targets = targets
+            .into_iter()
+            .flat_map(|range| mapping.map_range(range))
+            .collect();
+    }
+    targets
 }
 
-fn task_2(input_file: &str) -> i64 {
+fn parse_almanac(input_file: &str) -> Result<(Vec<i64>, Vec<Mapping>), ParseMappingError> {
     let file_content = read_to_string(input_file).unwrap();
     let mut chunks = file_content.split("\n\n");
     let seeds_str = chunks.next().unwrap().split_once(":").unwrap().1;
 
-    let mappings = chunks.map(Mapping::from).collect::<Vec<Mapping>>();
-
     let seeds = seeds_str
         .split_whitespace()
-        .map(|seed_str| seed_str.parse().unwrap())
-        .collect::<Vec<i64>>()
-        .chunks(2)
-        .map(|c| Vec::from(c))
-        .collect::<Vec<Vec<i64>>>();
+        .map(parse_value)
+        .collect::<Result<Vec<i64>, ParseMappingError>>()?;
+    let mappings = chunks
+        .map(Mapping::try_from)
+        .collect::<Result<Vec<Mapping>, ParseMappingError>>()?;
+    Ok((seeds, mappings))
+}
+
+fn task_1(input_file: &str, composed: bool) -> Result<i64, ParseMappingError> {
+    let (seeds, mappings) = parse_almanac(input_file)?;
 
+    if composed {
+        let composed_mapping = Mapping::compose(&mappings);
+        seeds
+            .iter()
+            .map(|&seed| composed_mapping.lookup(seed))
+            .min()
+            .ok_or(ParseMappingError::NoSeeds)
+    } else {
+        seeds
+            .iter()
+            .map(|&seed| map(seed, &mappings))
+            .min()
+            .ok_or(ParseMappingError::NoSeeds)
+    }
+}
+
+/// Seed ranges given as pairs of start and length, without the empty ones.
+fn seed_ranges(seeds: &[i64]) -> Vec<Range<i64>> {
     seeds
-        .par_iter()
-        .flat_map(|seed_range| {
-            println!("Processing range {:?}", seed_range);
-            seed_range[0]..(seed_range[0] + seed_range[1])
-        })
-        .map(|seed| map(seed, &mappings))
+        .chunks(2)
+        .map(|seed_range| seed_range[0]..(seed_range[0] + seed_range[1]))
+        .filter(|range| !range.is_empty())
+        .collect()
+}
+
+/// Lowest location reached from any of the seed ranges.
+fn lowest_location(
+    seed_ranges: Vec<Range<i64>>,
+    mappings: &Vec<Mapping>,
+) -> Result<i64, ParseMappingError> {
+    map_ranges(seed_ranges, mappings)
+        .iter()
+        .map(|range| range.start)
         .min()
-        .unwrap()
+        .ok_or(ParseMappingError::NoSeeds)
+}
+
+fn task_2(input_file: &str) -> Result<i64, ParseMappingError> {
+    let (seeds, mappings) = parse_almanac(input_file)?;
+
+    lowest_location(seed_ranges(&seeds), &mappings)
+}
+
+fn task_compose(input_file: &str) -> Result<String, ParseMappingError> {
+    let (_, mappings) = parse_almanac(input_file)?;
+    Ok(Mapping::compose(&mappings).to_string())
+}
+
+fn task_reverse(input_file: &str, location: Option<i64>) -> Result<String, ParseMappingError> {
+    let (seeds, mappings) = parse_almanac(input_file)?;
+    let seed_ranges = seed_ranges(&seeds);
+
+    let location = match location {
+        Some(location) => location,
+        None => lowest_location(seed_ranges.clone(), &mappings)?,
+    };
+    Ok(match find_seed(location, &seed_ranges, &mappings) {
+        Some((seed, range)) => format!(
+            "seed {} from range {}..{} leads to location {}",
+            seed, range.start, range.end, location
+        ),
+        None => format!("no seed leads to location {}", location),
+    })
+}
+
+fn task_convert(
+    input_file: &str,
+    from: &str,
+    to: &str,
+    values: &[i64],
+) -> Result<String, ParseMappingError> {
+    let (seeds, mappings) = parse_almanac(input_file)?;
+    let graph = CategoryGraph::new(&mappings);
+    let values = if values.is_empty() { &seeds } else { values };
+
+    Ok(values
+        .iter()
+        .map(|&value| match graph.map(value, from, to) {
+            Ok(target) => format!("{} {} -> {} {}", from, value, to, target),
+            Err(error @ GraphError::UnknownCategory(_)) => format!(
+                "{} {}: {}, known categories are {}",
+                from,
+                value,
+                error,
+                graph.categories().join(", ")
+            ),
+            Err(error) => format!("{} {}: {}", from, value, error),
+        })
+        .collect::<Vec<String>>()
+        .join("\n"))
+}
+
+fn task_validate(input_file: &str) -> String {
+    let issues = validate(&read_to_string(input_file).unwrap());
+    if issues.is_empty() {
+        return "no issues found".to_owned();
+    }
+    issues
+        .iter()
+        .map(|issue| issue.to_string())
+        .collect::<Vec<String>>()
+        .join("\n")
 }
 
 fn main() {
@@ -141,20 +497,34 @@ fn main() {
 
     let start = Instant::now();
     let result = match args.task {
-        Tasks::Task1 => task_1(&args.input_file),
-        Tasks::Task2 => task_2(&args.input_file),
+        Tasks::Task1 => task_1(&args.input_file, args.composed).map(|result| result.to_string()),
+        Tasks::Task2 => task_2(&args.input_file).map(|result| result.to_string()),
+        Tasks::Compose => task_compose(&args.input_file),
+        Tasks::Reverse => task_reverse(&args.input_file, args.location),
+        Tasks::Validate => Ok(task_validate(&args.input_file)),
+        Tasks::Convert => task_convert(&args.input_file, &args.from, &args.to, &args.values),
     };
     println!(
         "The task took {}ms to complete",
         start.elapsed().as_nanos() as f32 / 1_000_000.0
     );
-    println!("Result is {}", result);
+    match result {
+        Ok(result) => println!("Result is {}", result),
+        Err(error) => {
+            eprintln!("invalid almanac: {}", error);
+            std::process::exit(1);
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn parse_mapping(chunk: &str) -> Mapping {
+        Mapping::try_from(chunk).unwrap()
+    }
+
     #[test]
     fn test_mapping_map_1() {
         let single_mappings = vec![SingleMapping::new(50, 98, 2)];
@@ -211,13 +581,189 @@ mod tests {
         assert_eq!(mapping.map(110), 110);
     }
 
+    #[test]
+    fn test_mapping_map_range() {
+        let mapping = Mapping {
+            id: "abc".to_string(),
+            mappings: vec![
+                SingleMapping::new(50, 98, 2),
+                SingleMapping::new(52, 50, 48),
+            ],
+        };
+        assert_eq!(mapping.map_range(0..10), vec![0..10]);
+        assert_eq!(mapping.map_range(60..60), vec![]);
+        assert_eq!(mapping.map_range(60..70), vec![62..72]);
+        assert_eq!(
+            mapping.map_range(45..105),
+            vec![50..52, 52..100, 45..50, 100..105]
+        );
+
+        for range in [0..120, 49..51, 97..99] {
+            let mut mapped = mapping
+                .map_range(range.clone())
+                .into_iter()
+                .flatten()
+                .collect::<Vec<i64>>();
+            let mut expected = range
+                .map(|source| mapping.map(source))
+                .collect::<Vec<i64>>();
+            mapped.sort();
+            expected.sort();
+            assert_eq!(mapped, expected);
+        }
+    }
+
+    #[test]
+    fn test_zero_length_seed_range() {
+        let mappings = vec![parse_mapping("seed-to-soil map:\n50 98 2\n52 50 48")];
+        let seed_ranges = seed_ranges(&[5, 0, 98, 1, 60, 5]);
+        assert_eq!(seed_ranges, vec![98..99, 60..65]);
+        let starts = map_ranges(seed_ranges, &mappings)
+            .iter()
+            .map(|range| range.start)
+            .collect::<Vec<i64>>();
+        assert_eq!(starts, vec![50, 62]);
+    }
+
+    #[test]
+    fn test_compose() {
+        let mappings = vec![
+            Mapping {
+                id: "a-to-b map:".to_string(),
+                mappings: vec![
+                    SingleMapping::new(50, 98, 2),
+                    SingleMapping::new(52, 50, 48),
+                ],
+            },
+            Mapping {
+                id: "b-to-c map:".to_string(),
+                mappings: vec![
+                    SingleMapping::new(0, 15, 37),
+                    SingleMapping::new(37, 52, 2),
+                    SingleMapping::new(39, 0, 15),
+                ],
+            },
+        ];
+        let composed = Mapping::compose(&mappings);
+        assert!(composed
+            .mappings
+            .windows(2)
+            .all(|w| w[0].src_range_end() == w[1].src_range_start()));
+        assert_eq!(composed.mappings[0].src_range_start(), 0);
+        for source in 0..120 {
+            assert_eq!(composed.lookup(source), map(source, &mappings));
+        }
+        assert_eq!(composed.lookup(i64::MAX - 1), i64::MAX - 1);
+        assert_eq!(composed.lookup(-5), -5);
+    }
+
+    #[test]
+    fn test_compose_shifted_up_to_max() {
+        let mappings = vec![
+            parse_mapping(format!("a-to-b map:\n100 0 {}", i64::MAX).as_str()),
+            parse_mapping("b-to-c map:\n0 100 1"),
+        ];
+        let composed = Mapping::compose(&mappings);
+        assert_eq!(composed.lookup(0), 0);
+        assert_eq!(composed.lookup(5), 105);
+        assert_eq!(composed.lookup(i64::MAX - 101), i64::MAX - 1);
+    }
+
+    #[test]
+    fn test_inverse() {
+        let mapping = parse_mapping("seed-to-soil map:\n50 98 2\n52 50 48");
+        let inverse = mapping.inverse();
+        assert_eq!(inverse.id, "soil-to-seed map:");
+        assert_eq!(inverse.categories(), Some(("soil", "seed")));
+        assert_eq!(inverse.map(50), 98);
+        assert_eq!(inverse.map(99), 97);
+    }
+
+    #[test]
+    fn test_preimage() {
+        let mapping = parse_mapping("seed-to-soil map:\n50 98 2\n52 50 48");
+        assert_eq!(mapping.preimage(10), vec![10]);
+        assert_eq!(mapping.preimage(51), vec![99]);
+        assert_eq!(mapping.preimage(52), vec![50]);
+        assert_eq!(mapping.preimage(98), vec![96]);
+        assert_eq!(mapping.preimage(100), vec![100]);
+        for target in 0..110 {
+            for source in mapping.preimage(target) {
+                assert_eq!(mapping.map(source), target);
+            }
+        }
+    }
+
+    #[test]
+    fn test_find_seed() {
+        let mappings = vec![
+            parse_mapping("seed-to-soil map:\n10 0 5"),
+            parse_mapping("soil-to-fertilizer map:\n0 15 37\n37 52 2\n39 0 15"),
+        ];
+        assert_eq!(reverse_map(50, &mappings), vec![1, 11]);
+        assert_eq!(find_seed(50, &[0..5, 10..20], &mappings), Some((1, 0..5)));
+        assert_eq!(
+            find_seed(50, &[5..10, 10..20], &mappings),
+            Some((11, 10..20))
+        );
+        assert_eq!(find_seed(50, &[2..5, 20..30], &mappings), None);
+    }
+
+    #[test]
+    fn test_parse_mapping_errors() {
+        let parse = |line| SingleMapping::try_from(line).err();
+        assert_eq!(parse("50 98 2"), None);
+        assert_eq!(parse("50 98"), Some(ParseMappingError::MissingValue));
+        assert_eq!(
+            parse("50 98 2 7 x"),
+            Some(ParseMappingError::ExtraValue("7".to_owned()))
+        );
+        assert_eq!(
+            parse("50 x 2"),
+            Some(ParseMappingError::InvalidValue("x".to_owned()))
+        );
+        assert_eq!(
+            parse("99999999999999999999 0 1"),
+            Some(ParseMappingError::InvalidValue(
+                "99999999999999999999".to_owned()
+            ))
+        );
+        assert_eq!(
+            Mapping::try_from("seed-to-soil map:\n50 98 2\n52 50").err(),
+            Some(ParseMappingError::MissingValue)
+        );
+    }
+
+    #[test]
+    fn test_lowest_location() {
+        let mappings = vec![parse_mapping("seed-to-soil map:\n50 98 2\n52 50 48")];
+        assert_eq!(
+            lowest_location(seed_ranges(&[79, 14, 55, 13]), &mappings),
+            Ok(57)
+        );
+        assert_eq!(
+            lowest_location(seed_ranges(&[]), &mappings),
+            Err(ParseMappingError::NoSeeds)
+        );
+        assert_eq!(
+            lowest_location(seed_ranges(&[5, 0, 60, 0]), &mappings),
+            Err(ParseMappingError::NoSeeds)
+        );
+    }
+
+    #[test]
+    fn test_mapping_display() {
+        let mapping = parse_mapping("seed-to-soil map:\n50 98 2\n52 50 48");
+        assert_eq!(mapping.to_string(), "seed-to-soil map:\n50 98 2\n52 50 48");
+    }
+
     #[test]
     fn test_task_1() {
-        assert_eq!(task_1("./inputs/input_test.txt"), 35);
+        assert_eq!(task_1("./inputs/input_test.txt", false), Ok(35));
     }
 
     #[test]
     fn test_task_2() {
-        assert_eq!(task_2("./inputs/input_test.txt"), 46);
+        assert_eq!(task_2("./inputs/input_test.txt"), Ok(46));
     }
 }
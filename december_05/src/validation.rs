@@ -0,0 +1,213 @@
+use crate::{ParseMappingError, SingleMapping};
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, PartialEq)]
+pub enum IssueKind {
+    /// Source range overlapping the one on the given line, only the first of them is used.
+    OverlappingSources(usize),
+    /// Destination range overlapping the one on the given line, so the mapping is not injective.
+    OverlappingDestinations(usize),
+    EmptyRange,
+    /// End of the source or destination range does not fit into `i64`.
+    Overflow,
+    /// Line which is not a mapping of three integers.
+    Invalid(ParseMappingError),
+}
+
+/// Problem found in a mapping of the almanac, located by its 1-based line number.
+#[derive(Debug, PartialEq)]
+pub struct Issue {
+    pub mapping: String,
+    pub line: usize,
+    pub kind: IssueKind,
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {} ({}): ", self.line, self.mapping)?;
+        match &self.kind {
+            IssueKind::OverlappingSources(line) => {
+                write!(f, "source range overlaps the one on line {}", line)
+            }
+            IssueKind::OverlappingDestinations(line) => {
+                write!(f, "destination range overlaps the one on line {}", line)
+            }
+            IssueKind::EmptyRange => write!(f, "range is empty"),
+            IssueKind::Overflow => write!(f, "range end overflows i64"),
+            IssueKind::Invalid(error) => write!(f, "invalid mapping: {}", error),
+        }
+    }
+}
+
+/// Finds overlapping ranges, given as `(line, start, end)`, and returns every overlapping pair
+/// as the line of the later range together with the line of the earlier one.
+fn find_overlaps(mut ranges: Vec<(usize, i64, i64)>) -> Vec<(usize, usize)> {
+    ranges.sort_by_key(|&(line, start, _)| (start, line));
+    let mut overlaps = Vec::new();
+    // Ranges started so far which have not ended yet.
+    let mut open: Vec<(usize, i64)> = Vec::new();
+    for (line, start, end) in ranges {
+        open.retain(|&(_, open_end)| start < open_end);
+        for &(open_line, _) in &open {
+            overlaps.push((line.max(open_line), line.min(open_line)));
+        }
+        open.push((line, end));
+    }
+    overlaps.sort();
+    overlaps
+}
+
+fn validate_mapping(chunk: &str, header_line: usize) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    let mut sources = Vec::new();
+    let mut destinations = Vec::new();
+    let mut lines = chunk.lines();
+    let id = lines.next().unwrap_or_default();
+    let issue = |line: usize, kind: IssueKind| Issue {
+        mapping: id.to_owned(),
+        line,
+        kind,
+    };
+
+    for (i, text) in lines.enumerate() {
+        let line = header_line + i + 1;
+        let SingleMapping {
+            dest_range_start,
+            src_range_start,
+            range_len,
+        } = match SingleMapping::try_from(text) {
+            Ok(single) => single,
+            Err(error) => {
+                issues.push(issue(line, IssueKind::Invalid(error)));
+                continue;
+            }
+        };
+        match (
+            src_range_start.checked_add(range_len),
+            dest_range_start.checked_add(range_len),
+        ) {
+            _ if range_len <= 0 => issues.push(issue(line, IssueKind::EmptyRange)),
+            (Some(src_range_end), Some(dest_range_end)) => {
+                sources.push((line, src_range_start, src_range_end));
+                destinations.push((line, dest_range_start, dest_range_end));
+            }
+            _ => issues.push(issue(line, IssueKind::Overflow)),
+        }
+    }
+    for (line, other_line) in find_overlaps(sources) {
+        issues.push(issue(line, IssueKind::OverlappingSources(other_line)));
+    }
+    for (line, other_line) in find_overlaps(destinations) {
+        issues.push(issue(line, IssueKind::OverlappingDestinations(other_line)));
+    }
+    issues.sort_by_key(|issue| issue.line);
+    issues
+}
+
+/// Validates all mappings of the almanac, given as the content of the input file.
+pub fn validate(file_content: &str) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    let mut header_line = 1;
+    for (i, chunk) in file_content.split("\n\n").enumerate() {
+        if i > 0 && !chunk.trim().is_empty() {
+            issues.extend(validate_mapping(chunk, header_line));
+        }
+        header_line += chunk.lines().count() + 1;
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_valid() {
+        let almanac = "seeds: 79 14 55 13\n\nseed-to-soil map:\n50 98 2\n52 50 48\n";
+        assert_eq!(validate(almanac), vec![]);
+    }
+
+    #[test]
+    fn test_validate() {
+        let almanac = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+10 20 0
+37 30 10
+9223372036854775800 0 10
+60 0 15
+5 50 2";
+        let issues = validate(almanac);
+        let summary = issues
+            .iter()
+            .map(|issue| (issue.mapping.as_str(), issue.line, &issue.kind))
+            .collect::<Vec<(&str, usize, &IssueKind)>>();
+        let mapping = "soil-to-fertilizer map:";
+        assert_eq!(
+            summary,
+            vec![
+                (mapping, 9, &IssueKind::EmptyRange),
+                (mapping, 10, &IssueKind::OverlappingSources(8)),
+                (mapping, 11, &IssueKind::Overflow),
+                (mapping, 13, &IssueKind::OverlappingSources(8)),
+                (mapping, 13, &IssueKind::OverlappingDestinations(8)),
+            ]
+        );
+        assert_eq!(
+            issues[1].to_string(),
+            "line 10 (soil-to-fertilizer map:): source range overlaps the one on line 8"
+        );
+    }
+
+    #[test]
+    fn test_validate_all_overlaps() {
+        let almanac = "seeds: 79 14\n\nseed-to-soil map:\n100 0 10\n200 5 15\n300 8 1";
+        let issues = validate(almanac)
+            .into_iter()
+            .map(|issue| (issue.line, issue.kind))
+            .collect::<Vec<(usize, IssueKind)>>();
+        assert_eq!(
+            issues,
+            vec![
+                (5, IssueKind::OverlappingSources(4)),
+                (6, IssueKind::OverlappingSources(4)),
+                (6, IssueKind::OverlappingSources(5)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_invalid_lines() {
+        let almanac = "seeds: 79 14\n\nseed-to-soil map:\n50 98\n52 x 48\n99999999999999999999 0 1";
+        let issues = validate(almanac);
+        let kinds = issues
+            .iter()
+            .map(|issue| (issue.line, &issue.kind))
+            .collect::<Vec<(usize, &IssueKind)>>();
+        assert_eq!(
+            kinds,
+            vec![
+                (4, &IssueKind::Invalid(ParseMappingError::MissingValue)),
+                (
+                    5,
+                    &IssueKind::Invalid(ParseMappingError::InvalidValue("x".to_owned()))
+                ),
+                (
+                    6,
+                    &IssueKind::Invalid(ParseMappingError::InvalidValue(
+                        "99999999999999999999".to_owned()
+                    ))
+                ),
+            ]
+        );
+        assert_eq!(
+            issues[0].to_string(),
+            "line 4 (seed-to-soil map:): invalid mapping: expected three values"
+        );
+    }
+}
@@ -0,0 +1,181 @@
+use crate::hand::{Hand, HandError, HandType};
+use crate::rules::Rules;
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+
+/// Hand of the input with everything needed to explain its rank.
+#[derive(Debug)]
+pub struct ExplainedHand {
+    cards: String,
+    /// Cards with the wildcards promoted, as they are counted for the hand type.
+    promoted: String,
+    hand: Hand,
+    bid: u32,
+}
+
+impl ExplainedHand {
+    pub fn new(cards: &str, bid: u32, rules: &Rules) -> Result<Self, HandError> {
+        Ok(ExplainedHand {
+            cards: cards.to_owned(),
+            promoted: rules.promoted(cards)?,
+            hand: rules.evaluate(cards)?,
+            bid,
+        })
+    }
+
+    pub fn sort_key(&self) -> u64 {
+        self.hand.sort_key()
+    }
+
+    /// Line describing the hand once it is given its rank, starting from 1.
+    pub fn describe(&self, rank: usize) -> String {
+        let cards = if self.promoted == self.cards {
+            self.cards.clone()
+        } else {
+            format!("{} as {}", self.cards, self.promoted)
+        };
+        format!(
+            "#{:<4} {:<20} {:<16} bid {:>5} wins {}",
+            rank,
+            cards,
+            self.hand.hand_type().name(),
+            self.bid,
+            rank as u128 * self.bid as u128
+        )
+    }
+}
+
+/// What decided the comparison of two hands.
+#[derive(Debug, PartialEq)]
+pub enum Decision {
+    /// The hands have different types, given in the order of the compared hands.
+    HandType(HandType, HandType),
+    /// The hands have the same type and their tie-break cards first differ at the position,
+    /// starting from 0.
+    TieBreak {
+        hand_type: HandType,
+        position: usize,
+        cards: (char, char),
+    },
+    /// The hands have the same type and the same tie-break cards.
+    Tie(HandType),
+}
+
+/// Outcome of comparing two hands, displayed as an explanation of the winner.
+#[derive(Debug, PartialEq)]
+pub struct Comparison {
+    hands: (String, String),
+    ordering: Ordering,
+    decision: Decision,
+}
+
+pub fn compare(first: &str, second: &str, rules: &Rules) -> Result<Comparison, HandError> {
+    let (first_hand, second_hand) = (rules.evaluate(first)?, rules.evaluate(second)?);
+    let (first_type, second_type) = (first_hand.hand_type(), second_hand.hand_type());
+    let decision = if first_type != second_type {
+        Decision::HandType(first_type.clone(), second_type.clone())
+    } else {
+        match first_hand
+            .tie_break()
+            .iter()
+            .zip(second_hand.tie_break())
+            .position(|(first_rank, second_rank)| first_rank != second_rank)
+        {
+            Some(position) => Decision::TieBreak {
+                hand_type: first_type.clone(),
+                position,
+                cards: (
+                    rules.card(first_hand.tie_break()[position]),
+                    rules.card(second_hand.tie_break()[position]),
+                ),
+            },
+            None => Decision::Tie(first_type.clone()),
+        }
+    };
+    Ok(Comparison {
+        hands: (first.to_owned(), second.to_owned()),
+        ordering: first_hand.cmp(&second_hand),
+        decision,
+    })
+}
+
+impl Display for Comparison {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (winner, loser) = match self.ordering {
+            Ordering::Less => (&self.hands.1, &self.hands.0),
+            _ => (&self.hands.0, &self.hands.1),
+        };
+        let swap = |pair: (String, String)| match self.ordering {
+            Ordering::Less => (pair.1, pair.0),
+            _ => pair,
+        };
+        match &self.decision {
+            Decision::HandType(first, second) => {
+                let (winning, losing) = swap((first.name(), second.name()));
+                write!(f, "{} wins: {} beats {}", winner, winning, losing)
+            }
+            Decision::TieBreak {
+                hand_type,
+                position,
+                cards,
+            } => {
+                let (winning, losing) = swap((cards.0.to_string(), cards.1.to_string()));
+                write!(
+                    f,
+                    "{} wins: both are {}, {} beats {} at tie-break position {}",
+                    winner,
+                    hand_type,
+                    winning,
+                    losing,
+                    position + 1
+                )
+            }
+            Decision::Tie(hand_type) => write!(
+                f,
+                "{} and {} tie: both are {} with the same tie-break cards",
+                winner, loser, hand_type
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare() {
+        let rules = Rules::part_1();
+        let comparison = compare("KK677", "KTJJT", &rules).unwrap();
+        assert_eq!(comparison.ordering, Ordering::Greater);
+        assert_eq!(
+            comparison.to_string(),
+            "KK677 wins: both are two pairs, K beats T at tie-break position 2"
+        );
+        assert_eq!(
+            compare("32T3K", "T55J5", &rules).unwrap().to_string(),
+            "T55J5 wins: three of a kind beats one pair"
+        );
+        assert_eq!(
+            compare("32T3K", "32T3K", &rules).unwrap().decision,
+            Decision::Tie(HandType::from_counts(&[2, 1, 1, 1]))
+        );
+        assert_eq!(
+            compare("32T3K", "32X3K", &rules),
+            Err(HandError::UnknownCard('X'))
+        );
+    }
+
+    #[test]
+    fn test_describe() {
+        let hand = ExplainedHand::new("KTJJT", 220, &Rules::part_2()).unwrap();
+        assert_eq!(
+            hand.describe(5),
+            "#5    KTJJT as KTTTT       four of a kind   bid   220 wins 1100"
+        );
+        let hand = ExplainedHand::new("KK677", 28, &Rules::part_1()).unwrap();
+        assert!(hand
+            .describe(4)
+            .starts_with("#4    KK677                two pairs"));
+    }
+}
@@ -0,0 +1,240 @@
+use std::fmt::{Display, Formatter};
+
+/// Largest hand which fits into the packed sort key.
+pub const MAX_HAND_SIZE: usize = 8;
+/// Number of distinct card ranks which fit into the packed sort key.
+pub const MAX_RANKS: usize = 16;
+/// Count placed into signatures of hand types which are not based on equal cards, larger than
+/// any count of equal cards.
+const EXTENSION: u8 = 15;
+
+#[derive(Debug, PartialEq)]
+pub enum HandError {
+    EmptyHand,
+    TooManyCards(usize),
+    UnknownCard(char),
+    TooManyRanks(usize),
+    /// Line of the input without a bid after the cards.
+    MissingBid(String),
+    InvalidBid(String),
+    /// Hand with another number of cards than the first hand of the input, which is given.
+    MixedHandSizes(String, usize),
+}
+
+impl Display for HandError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HandError::EmptyHand => write!(f, "hand has no cards"),
+            HandError::TooManyCards(num_cards) => write!(
+                f,
+                "hand has {} cards, at most {} are supported",
+                num_cards, MAX_HAND_SIZE
+            ),
+            HandError::UnknownCard(card) => write!(f, "unknown card '{}'", card),
+            HandError::TooManyRanks(num_ranks) => write!(
+                f,
+                "ranking has {} cards, at most {} are supported",
+                num_ranks, MAX_RANKS
+            ),
+            HandError::MissingBid(line) => write!(f, "line '{}' has no bid", line),
+            HandError::InvalidBid(bid) => write!(f, "invalid bid '{}'", bid),
+            HandError::MixedHandSizes(cards, hand_size) => write!(
+                f,
+                "hand '{}' does not have {} cards like the first hand",
+                cards, hand_size
+            ),
+        }
+    }
+}
+
+/// Category of a hand, described by its count signature: the numbers of equal cards sorted
+/// from the most numerous, e.g. `[3, 2]` for a full house. Signatures compare
+/// lexicographically, which generalises the ordering of the five card categories to hands of
+/// any size.
+#[derive(Ord, PartialOrd, Eq, PartialEq, Debug, Clone)]
+pub struct HandType {
+    signature: Vec<u8>,
+}
+
+impl HandType {
+    /// Hand type of the numbers of equal cards sorted from the most numerous.
+    pub fn from_counts(counts: &[u8]) -> Self {
+        HandType {
+            signature: counts.to_vec(),
+        }
+    }
+
+    /// Signature the straights and flushes extend, so that they rank right above it: `n - 2`
+    /// equal cards and a single one, e.g. three of a kind for five cards, or a high card for
+    /// hands too small to hold such a group.
+    fn extension_base(hand_size: usize) -> [u8; 2] {
+        match hand_size {
+            0 | 1 => [0, 1],
+            2 | 3 => [1, 1],
+            _ => [hand_size as u8 - 2, 1],
+        }
+    }
+
+    /// Cards of consecutive ranks, ranked above `n - 2` equal cards.
+    pub fn straight(hand_size: usize) -> Self {
+        let [count, single] = HandType::extension_base(hand_size);
+        HandType::from_counts(&[count, single, EXTENSION])
+    }
+
+    /// Cards of a single suit, ranked above a straight.
+    pub fn flush(hand_size: usize) -> Self {
+        let [count, single] = HandType::extension_base(hand_size);
+        HandType::from_counts(&[count, single, EXTENSION, EXTENSION])
+    }
+
+    /// Cards of consecutive ranks and a single suit, ranked right below all cards being equal.
+    pub fn straight_flush(hand_size: usize) -> Self {
+        HandType::from_counts(&[hand_size.saturating_sub(1) as u8, EXTENSION])
+    }
+
+    pub fn name(&self) -> String {
+        if self.signature.last() == Some(&EXTENSION) {
+            return match self.signature.len() {
+                2 => "straight flush",
+                3 => "straight",
+                _ => "flush",
+            }
+            .to_owned();
+        }
+        let groups = self
+            .signature
+            .iter()
+            .copied()
+            .filter(|&count| count > 1)
+            .collect::<Vec<u8>>();
+        match groups[..] {
+            [] => "high card".to_owned(),
+            [2] => "one pair".to_owned(),
+            [2, 2] => "two pairs".to_owned(),
+            [3] => "three of a kind".to_owned(),
+            [3, 2] => "full house".to_owned(),
+            [4] => "four of a kind".to_owned(),
+            [5] => "five of a kind".to_owned(),
+            [count] => format!("{} of a kind", count),
+            _ => format!(
+                "groups of {}",
+                groups
+                    .iter()
+                    .map(|count| count.to_string())
+                    .collect::<Vec<String>>()
+                    .join("+")
+            ),
+        }
+    }
+}
+
+impl Display for HandType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Evaluated hand, ordered by its type and then by the tie-break ranks of its cards.
+#[derive(Ord, PartialOrd, Eq, PartialEq, Debug)]
+pub struct Hand {
+    hand_type: HandType,
+    tie_break: Vec<u8>,
+}
+
+impl Hand {
+    pub fn new(hand_type: HandType, tie_break: Vec<u8>) -> Self {
+        Hand {
+            hand_type,
+            tie_break,
+        }
+    }
+
+    pub fn hand_type(&self) -> &HandType {
+        &self.hand_type
+    }
+
+    pub fn tie_break(&self) -> &[u8] {
+        &self.tie_break
+    }
+
+    /// Key ordering hands of the same size like [`Hand`] does, packing the signature of the
+    /// hand type into the upper half and the tie-break ranks into the lower half, four bits
+    /// per value.
+    pub fn sort_key(&self) -> u64 {
+        let pack = |values: &[u8]| {
+            (0..MAX_HAND_SIZE).fold(0u64, |key, i| {
+                (key << 4) | values.get(i).copied().unwrap_or(0) as u64
+            })
+        };
+        (pack(&self.hand_type.signature) << 32) | pack(&self.tie_break)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_comparator_different_hand_type_with_same_card() {
+        let hand = |counts: &[u8]| Hand::new(HandType::from_counts(counts), vec![0; 5]);
+        assert!(hand(&[1, 1, 1, 1, 1]) < hand(&[2, 1, 1, 1]));
+        assert!(hand(&[2, 1, 1, 1]) < hand(&[2, 2, 1]));
+        assert!(hand(&[2, 2, 1]) < hand(&[3, 1, 1]));
+        assert!(hand(&[3, 1, 1]) < hand(&[3, 2]));
+        assert!(hand(&[3, 2]) < hand(&[4, 1]));
+        assert!(hand(&[4, 1]) < hand(&[5]));
+        assert!(HandType::from_counts(&[3, 1, 1]) < HandType::straight(5));
+        assert!(HandType::straight(5) < HandType::flush(5));
+        assert!(HandType::flush(5) < HandType::from_counts(&[3, 2]));
+        assert!(HandType::from_counts(&[4, 1]) < HandType::straight_flush(5));
+        assert!(HandType::straight_flush(5) < HandType::from_counts(&[5]));
+    }
+
+    #[test]
+    fn test_extensions_of_small_hands() {
+        for (hand_size, high_card, all_equal) in
+            [(2, vec![1, 1], vec![2]), (3, vec![1, 1, 1], vec![3])]
+        {
+            let high_card = HandType::from_counts(&high_card);
+            let all_equal = HandType::from_counts(&all_equal);
+            assert!(high_card < HandType::straight(hand_size));
+            assert!(HandType::straight(hand_size) < HandType::flush(hand_size));
+            assert!(HandType::flush(hand_size) < HandType::straight_flush(hand_size));
+            assert!(HandType::straight_flush(hand_size) < all_equal);
+        }
+        assert!(HandType::flush(3) < HandType::from_counts(&[2, 1]));
+        assert!(HandType::flush(4) < HandType::from_counts(&[2, 2]));
+    }
+
+    #[test]
+    fn test_name() {
+        let name = |counts: &[u8]| HandType::from_counts(counts).name();
+        assert_eq!(name(&[1, 1, 1, 1, 1]), "high card");
+        assert_eq!(name(&[2, 2, 1]), "two pairs");
+        assert_eq!(name(&[3, 2]), "full house");
+        assert_eq!(name(&[6, 1]), "6 of a kind");
+        assert_eq!(name(&[2, 2, 2]), "groups of 2+2+2");
+        assert_eq!(HandType::straight(5).name(), "straight");
+        assert_eq!(HandType::flush(5).name(), "flush");
+        assert_eq!(HandType::straight_flush(5).name(), "straight flush");
+    }
+
+    #[test]
+    fn test_sort_key() {
+        let hands = [
+            Hand::new(
+                HandType::from_counts(&[1, 1, 1, 1, 1]),
+                vec![12, 11, 10, 9, 8],
+            ),
+            Hand::new(HandType::from_counts(&[2, 1, 1, 1]), vec![0, 0, 1, 2, 3]),
+            Hand::new(HandType::from_counts(&[2, 1, 1, 1]), vec![0, 1, 0, 2, 3]),
+            Hand::new(HandType::straight(5), vec![0, 1, 2, 3, 4]),
+            Hand::new(HandType::from_counts(&[3, 2]), vec![0, 0, 0, 1, 1]),
+            Hand::new(HandType::from_counts(&[5]), vec![0; 5]),
+        ];
+        for pair in hands.windows(2) {
+            assert!(pair[0] < pair[1]);
+            assert!(pair[0].sort_key() < pair[1].sort_key());
+        }
+    }
+}
@@ -1,7 +1,12 @@
-mod task_1;
-mod task_2;
+mod explain;
+mod hand;
+mod rules;
 
-use clap::{Parser, ValueEnum};
+use crate::explain::{compare, ExplainedHand};
+use crate::hand::HandError;
+use crate::rules::{Rules, TieBreak};
+use clap::{Parser, Subcommand, ValueEnum};
+use std::fs::read_to_string;
 use std::time::Instant;
 
 #[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
@@ -10,22 +15,161 @@ pub enum Tasks {
     Task2,
 }
 
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Explain which of two hands wins instead of reading an input file
+    Compare { first: String, second: String },
+}
+
 #[derive(Debug, Parser)]
+#[command(subcommand_negates_reqs = true)]
 pub struct Cli {
     /// Input file to read
-    input_file: String,
+    #[arg(required = true)]
+    input_file: Option<String>,
     /// Number of lines to read
-    #[structopt(short = 't')]
-    task: Tasks,
+    #[structopt(short = 't', required = true)]
+    task: Option<Tasks>,
+    /// Cards ordered from the weakest to the strongest, overriding the ranking of the task
+    #[arg(long)]
+    ranking: Option<String>,
+    /// Cards acting as jokers, overriding the jokers of the task
+    #[arg(long)]
+    wildcards: Option<String>,
+    /// How hands of the same type are ordered
+    #[arg(long, value_enum, default_value_t = TieBreak::Positional)]
+    tie_break: TieBreak,
+    /// Rank cards of consecutive ranks as a straight
+    #[arg(long)]
+    straights: bool,
+    /// Comma separated groups of cards ranked as a flush when a hand holds a single group
+    #[arg(long, value_delimiter = ',')]
+    suits: Vec<String>,
+    /// Print every hand sorted by rank with its type, promoted jokers and winnings
+    #[arg(long)]
+    explain: bool,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+fn split_line(line: &str) -> Result<(&str, u32), HandError> {
+    let (cards_str, bid_str) = line
+        .split_once(" ")
+        .ok_or_else(|| HandError::MissingBid(line.to_owned()))?;
+    let bid = bid_str
+        .parse()
+        .map_err(|_| HandError::InvalidBid(bid_str.to_owned()))?;
+    Ok((cards_str, bid))
+}
+
+/// Cards and bids of every line, all hands having the same number of cards since sort keys
+/// only order hands of the same size.
+fn split_lines(file_content: &str) -> Result<Vec<(&str, u32)>, HandError> {
+    let hands = file_content
+        .lines()
+        .map(split_line)
+        .collect::<Result<Vec<(&str, u32)>, HandError>>()?;
+    if let Some(&(first, _)) = hands.first() {
+        let hand_size = first.chars().count();
+        if let Some(&(cards, _)) = hands
+            .iter()
+            .find(|(cards, _)| cards.chars().count() != hand_size)
+        {
+            return Err(HandError::MixedHandSizes(cards.to_owned(), hand_size));
+        }
+    }
+    Ok(hands)
+}
+
+fn parse_hand(cards: &str, bid: u32, rules: &Rules) -> Result<(u64, u32), HandError> {
+    Ok((rules.evaluate(cards)?.sort_key(), bid))
+}
+
+/// Hands sorted by their sort key, each with its rank starting from 1. Equal hands keep the
+/// order of the input.
+fn ranked<T>(mut hands: Vec<T>, sort_key: impl Fn(&T) -> u64) -> Vec<(usize, T)> {
+    hands.sort_by_key(sort_key);
+    hands
+        .into_iter()
+        .enumerate()
+        .map(|(i, hand)| (i + 1, hand))
+        .collect()
+}
+
+/// Sum of the bids of the hands, given with their sort key, multiplied by their rank.
+fn total_winnings(hands: Vec<(u64, u32)>) -> u128 {
+    ranked(hands, |&(sort_key, _)| sort_key)
+        .iter()
+        .map(|&(rank, (_, bid))| rank as u128 * bid as u128)
+        .sum()
+}
+
+fn run(input_file: &str, rules: &Rules) -> Result<u128, HandError> {
+    let file_content = read_to_string(input_file).unwrap();
+    let hands = split_lines(&file_content)?
+        .into_iter()
+        .map(|(cards, bid)| parse_hand(cards, bid, rules))
+        .collect::<Result<Vec<(u64, u32)>, HandError>>()?;
+    Ok(total_winnings(hands))
+}
+
+/// Lines describing every hand of the input, from the weakest to the strongest.
+fn explain(input_file: &str, rules: &Rules) -> Result<Vec<String>, HandError> {
+    let file_content = read_to_string(input_file).unwrap();
+    let hands = split_lines(&file_content)?
+        .into_iter()
+        .map(|(cards, bid)| ExplainedHand::new(cards, bid, rules))
+        .collect::<Result<Vec<ExplainedHand>, HandError>>()?;
+    Ok(ranked(hands, ExplainedHand::sort_key)
+        .iter()
+        .map(|(rank, hand)| hand.describe(*rank))
+        .collect())
 }
 
 fn main() {
     let args = Cli::parse();
 
+    // Only the compare subcommand runs without a task, using the rules of task 1 by default.
+    let mut rules = match args.task.unwrap_or(Tasks::Task1) {
+        Tasks::Task1 => Rules::part_1(),
+        Tasks::Task2 => Rules::part_2(),
+    }
+    .with_tie_break(args.tie_break)
+    .with_straights(args.straights)
+    .with_suits(&args.suits);
+    if let Some(ranking) = &args.ranking {
+        rules = rules.with_ranking(ranking);
+    }
+    if let Some(wildcards) = &args.wildcards {
+        rules = rules.with_wildcards(wildcards);
+    }
+
+    if let Some(Command::Compare { first, second }) = &args.command {
+        match compare(first, second, &rules) {
+            Ok(comparison) => println!("{}", comparison),
+            Err(error) => println!("invalid hand: {}", error),
+        }
+        return;
+    }
+    let input_file = args.input_file.unwrap();
+
+    if args.explain {
+        match explain(&input_file, &rules) {
+            Ok(lines) => lines.iter().for_each(|line| println!("{}", line)),
+            Err(error) => {
+                eprintln!("invalid input: {}", error);
+                std::process::exit(1);
+            }
+        }
+    }
+
     let start = Instant::now();
-    let result = match args.task {
-        Tasks::Task1 => crate::task_1::run(&args.input_file),
-        Tasks::Task2 => crate::task_2::run(&args.input_file),
+    let result = match run(&input_file, &rules) {
+        Ok(total_winnings) => total_winnings,
+        Err(error) => {
+            eprintln!("invalid input: {}", error);
+            std::process::exit(1);
+        }
     };
     println!(
         "The task took {}ms to complete",
@@ -33,3 +177,97 @@ fn main() {
     );
     println!("Result is {}", result);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_line(line: &str, rules: &Rules) -> Result<(u64, u32), HandError> {
+        let (cards, bid) = split_line(line)?;
+        parse_hand(cards, bid, rules)
+    }
+
+    #[test]
+    fn test_parse_line_errors() {
+        let rules = Rules::part_1();
+        assert_eq!(
+            parse_line("32T3K", &rules),
+            Err(HandError::MissingBid("32T3K".to_owned()))
+        );
+        assert_eq!(
+            parse_line("32T3K 7x", &rules),
+            Err(HandError::InvalidBid("7x".to_owned()))
+        );
+        assert_eq!(
+            parse_line("32T3K -5", &rules),
+            Err(HandError::InvalidBid("-5".to_owned()))
+        );
+        assert!(parse_line("32T3K 765", &rules).is_ok());
+    }
+
+    #[test]
+    fn test_mixed_hand_sizes() {
+        assert_eq!(
+            split_lines("34 1\n342 2"),
+            Err(HandError::MixedHandSizes("342".to_owned(), 2))
+        );
+        assert_eq!(
+            split_lines("342 1\n34 2"),
+            Err(HandError::MixedHandSizes("34".to_owned(), 3))
+        );
+        assert_eq!(split_lines("34 1\n43 2"), Ok(vec![("34", 1), ("43", 2)]));
+        assert_eq!(split_lines(""), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_tied_hands() {
+        let rules = Rules::part_1().with_tie_break(TieBreak::Poker);
+        let hands = |lines: &[&str]| {
+            lines
+                .iter()
+                .map(|line| parse_line(line, &rules).unwrap())
+                .collect::<Vec<(u64, u32)>>()
+        };
+        // The tied QQ hands take the second and third ranks in the order of the input.
+        let lines = ["QQ243 5", "23K44 100", "QQ234 10", "KKK23 1000"];
+        assert_eq!(
+            total_winnings(hands(&lines)),
+            100 + 2 * 5 + 3 * 10 + 4 * 1000
+        );
+        let lines = ["QQ234 10", "KKK23 1000", "QQ243 5", "23K44 100"];
+        assert_eq!(
+            total_winnings(hands(&lines)),
+            100 + 2 * 10 + 3 * 5 + 4 * 1000
+        );
+    }
+
+    #[test]
+    fn test_large_winnings() {
+        let hands = (0..3).map(|i| (i, u32::MAX)).collect::<Vec<(u64, u32)>>();
+        assert_eq!(total_winnings(hands), 6 * u32::MAX as u128);
+    }
+
+    #[test]
+    fn test_parse_cli() {
+        let args = Cli::try_parse_from(["december_07", "compare", "KK677", "KTJJT"]).unwrap();
+        assert_eq!(args.task, None);
+        assert!(matches!(args.command, Some(Command::Compare { .. })));
+        let args = Cli::try_parse_from(["december_07", "-t", "task2", "compare", "KK677", "KTJJT"])
+            .unwrap();
+        assert_eq!(args.task, Some(Tasks::Task2));
+        let args = Cli::try_parse_from(["december_07", "input.txt", "-t", "task1"]).unwrap();
+        assert_eq!(args.input_file.as_deref(), Some("input.txt"));
+        assert!(Cli::try_parse_from(["december_07", "input.txt"]).is_err());
+        assert!(Cli::try_parse_from(["december_07", "-t", "task1"]).is_err());
+    }
+
+    #[test]
+    fn test_task_1() {
+        assert_eq!(run("./inputs/input_test.txt", &Rules::part_1()), Ok(6440));
+    }
+
+    #[test]
+    fn test_task_2() {
+        assert_eq!(run("./inputs/input_test.txt", &Rules::part_2()), Ok(5905));
+    }
+}
@@ -0,0 +1,417 @@
+use crate::hand::{Hand, HandError, HandType, MAX_HAND_SIZE, MAX_RANKS};
+use clap::ValueEnum;
+use std::collections::HashMap;
+
+/// How hands of the same type are ordered.
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TieBreak {
+    /// Compare the cards one by one in the order they were dealt
+    Positional,
+    /// Compare the cards grouped by their number, from the most numerous and strongest group,
+    /// as in poker
+    Poker,
+}
+
+/// Rules of a Camel Cards game.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rules {
+    /// Cards ordered from the weakest to the strongest.
+    ranking: Vec<char>,
+    /// Cards acting as whatever card makes the strongest hand type.
+    wildcards: Vec<char>,
+    tie_break: TieBreak,
+    /// Whether cards of consecutive ranks form a straight.
+    straights: bool,
+    /// Groups of cards forming a flush when all cards of a hand belong to one of them.
+    suits: Vec<Vec<char>>,
+}
+
+impl Rules {
+    pub fn new(ranking: &str, wildcards: &str, tie_break: TieBreak) -> Self {
+        Rules {
+            ranking: ranking.chars().collect(),
+            wildcards: wildcards.chars().collect(),
+            tie_break,
+            straights: false,
+            suits: Vec::new(),
+        }
+    }
+
+    /// Rules of the first part of the puzzle.
+    pub fn part_1() -> Self {
+        Rules::new("23456789TJQKA", "", TieBreak::Positional)
+    }
+
+    /// Rules of the second part of the puzzle, where `J` is a joker and the weakest card.
+    pub fn part_2() -> Self {
+        Rules::new("J23456789TQKA", "J", TieBreak::Positional)
+    }
+
+    pub fn with_ranking(self, ranking: &str) -> Self {
+        Rules {
+            ranking: ranking.chars().collect(),
+            ..self
+        }
+    }
+
+    pub fn with_wildcards(self, wildcards: &str) -> Self {
+        Rules {
+            wildcards: wildcards.chars().collect(),
+            ..self
+        }
+    }
+
+    pub fn with_tie_break(self, tie_break: TieBreak) -> Self {
+        Rules { tie_break, ..self }
+    }
+
+    pub fn with_straights(self, straights: bool) -> Self {
+        Rules { straights, ..self }
+    }
+
+    pub fn with_suits(self, suits: &[String]) -> Self {
+        Rules {
+            suits: suits.iter().map(|suit| suit.chars().collect()).collect(),
+            ..self
+        }
+    }
+
+    pub fn card_rank(&self, card: char) -> Result<u8, HandError> {
+        match self.ranking.iter().position(|&c| c == card) {
+            Some(rank) => Ok(rank as u8),
+            None => Err(HandError::UnknownCard(card)),
+        }
+    }
+
+    /// Cards of the hand after checking they are supported by these rules.
+    fn cards(&self, cards: &str) -> Result<Vec<char>, HandError> {
+        if self.ranking.len() > MAX_RANKS {
+            return Err(HandError::TooManyRanks(self.ranking.len()));
+        }
+        let cards = cards.chars().collect::<Vec<char>>();
+        if cards.is_empty() {
+            return Err(HandError::EmptyHand);
+        }
+        if cards.len() > MAX_HAND_SIZE {
+            return Err(HandError::TooManyCards(cards.len()));
+        }
+        for &card in &cards {
+            self.card_rank(card)?;
+        }
+        Ok(cards)
+    }
+
+    /// Card of the given rank.
+    pub fn card(&self, rank: u8) -> char {
+        self.ranking[rank as usize]
+    }
+
+    /// Rank of a card already checked by [`Rules::cards`].
+    fn rank(&self, card: char) -> u8 {
+        self.ranking.iter().position(|&c| c == card).unwrap() as u8
+    }
+
+    fn is_wildcard(&self, card: char) -> bool {
+        self.wildcards.contains(&card)
+    }
+
+    fn count_cards(cards: impl Iterator<Item = char>) -> HashMap<char, u8> {
+        let mut mapping: HashMap<char, u8> = HashMap::new();
+        for card in cards {
+            *mapping.entry(card).or_insert(0) += 1;
+        }
+        mapping
+    }
+
+    /// Cards of the hand with every wildcard replaced by the most numerous other card, the
+    /// strongest one in case of a tie, or by the strongest card if all cards are wildcards.
+    fn promote(&self, cards: &[char]) -> Vec<char> {
+        let mapping = Rules::count_cards(
+            cards
+                .iter()
+                .copied()
+                .filter(|&card| !self.is_wildcard(card)),
+        );
+        let best = mapping
+            .iter()
+            .max_by_key(|(&card, &count)| (count, self.rank(card)))
+            .map(|(&card, _)| card)
+            .unwrap_or(*self.ranking.last().unwrap());
+        cards
+            .iter()
+            .map(|&card| if self.is_wildcard(card) { best } else { card })
+            .collect()
+    }
+
+    /// Cards of the hand as they are counted for its type, with the wildcards promoted.
+    pub fn promoted(&self, cards: &str) -> Result<String, HandError> {
+        Ok(self.promote(&self.cards(cards)?).into_iter().collect())
+    }
+
+    /// Numbers of equal cards, from the most numerous, with the wildcards added to the first.
+    fn card_counts(&self, cards: &[char]) -> Vec<u8> {
+        let mapping = Rules::count_cards(self.promote(cards).into_iter());
+        let mut counts = mapping.values().cloned().collect::<Vec<u8>>();
+        counts.sort();
+        counts.reverse();
+        counts
+    }
+
+    /// Whether the cards, with wildcards filling the gaps, have consecutive ranks.
+    fn is_straight(&self, cards: &[char]) -> bool {
+        let mut ranks = cards
+            .iter()
+            .filter(|&&card| !self.is_wildcard(card))
+            .map(|&card| self.rank(card))
+            .collect::<Vec<u8>>();
+        ranks.sort();
+        let num_ranks = ranks.len();
+        ranks.dedup();
+        match (ranks.first(), ranks.last()) {
+            (Some(first), Some(last)) => {
+                ranks.len() == num_ranks && ((last - first) as usize) < cards.len()
+            }
+            _ => true,
+        }
+    }
+
+    /// Whether all cards which are not wildcards belong to the same suit.
+    fn is_flush(&self, cards: &[char]) -> bool {
+        self.suits.iter().any(|suit| {
+            cards
+                .iter()
+                .all(|card| self.is_wildcard(*card) || suit.contains(card))
+        })
+    }
+
+    fn hand_type(&self, cards: &[char]) -> HandType {
+        let hand_type = HandType::from_counts(&self.card_counts(cards));
+        let straight = self.straights && self.is_straight(cards);
+        let flush = self.is_flush(cards);
+        let extension = match (straight, flush) {
+            (true, true) => HandType::straight_flush(cards.len()),
+            (true, false) => HandType::straight(cards.len()),
+            (false, true) => HandType::flush(cards.len()),
+            (false, false) => return hand_type,
+        };
+        hand_type.max(extension)
+    }
+
+    /// Ranks of the promoted cards ordered by the number of their occurrences and then by
+    /// their rank, both descending.
+    fn poker_tie_break(&self, cards: &[char]) -> Vec<u8> {
+        let promoted = self.promote(cards);
+        let mapping = Rules::count_cards(promoted.iter().copied());
+        let mut ranks = promoted
+            .iter()
+            .map(|&card| (mapping[&card], self.rank(card)))
+            .collect::<Vec<(u8, u8)>>();
+        ranks.sort();
+        ranks.reverse();
+        ranks.into_iter().map(|(_, rank)| rank).collect()
+    }
+
+    pub fn evaluate(&self, cards: &str) -> Result<Hand, HandError> {
+        let cards = self.cards(cards)?;
+        let tie_break = match self.tie_break {
+            TieBreak::Positional => cards.iter().map(|&card| self.rank(card)).collect(),
+            TieBreak::Poker => self.poker_tie_break(&cards),
+        };
+        Ok(Hand::new(self.hand_type(&cards), tie_break))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_card_rank() {
+        let rules = Rules::part_1();
+        let ranks = "23456789TJQKA"
+            .chars()
+            .map(|card| rules.card_rank(card).unwrap())
+            .collect::<Vec<u8>>();
+        assert!(ranks.windows(2).all(|w| w[0] < w[1]));
+
+        let rules = Rules::part_2();
+        assert!(rules.card_rank('J').unwrap() < rules.card_rank('2').unwrap());
+        assert!(rules.card_rank('T').unwrap() < rules.card_rank('Q').unwrap());
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let rules = Rules::part_1();
+        let hand_type = |cards| rules.hand_type(&rules.cards(cards).unwrap()).name();
+        assert_eq!(hand_type("AAAAA"), "five of a kind");
+        assert_eq!(hand_type("AA8AA"), "four of a kind");
+        assert_eq!(hand_type("23332"), "full house");
+        assert_eq!(hand_type("TTT98"), "three of a kind");
+        assert_eq!(hand_type("23432"), "two pairs");
+        assert_eq!(hand_type("A23A4"), "one pair");
+        assert_eq!(hand_type("23456"), "high card");
+        assert_eq!(hand_type("QJJQ2"), "two pairs");
+    }
+
+    #[test]
+    fn test_evaluate_with_joker() {
+        let rules = Rules::part_2();
+        let hand_type = |cards| rules.hand_type(&rules.cards(cards).unwrap()).name();
+        assert_eq!(hand_type("QJJQ2"), "four of a kind");
+        assert_eq!(hand_type("JJJJJ"), "five of a kind");
+        assert_eq!(hand_type("T55J5"), "four of a kind");
+    }
+
+    #[test]
+    fn test_comparator_same_hand_type_with_different_cards() {
+        let rules = Rules::part_1();
+        assert!(rules.evaluate("33332").unwrap() > rules.evaluate("2AAAA").unwrap());
+        let rules = Rules::part_2();
+        assert!(rules.evaluate("33332").unwrap() > rules.evaluate("2AAAA").unwrap());
+        assert!(rules.evaluate("QQQQ2").unwrap() > rules.evaluate("JKKK2").unwrap());
+    }
+
+    #[test]
+    fn test_custom_ranking() {
+        let rules = Rules::part_1().with_ranking("AKQJT98765432");
+        assert!(rules.evaluate("22345").unwrap() > rules.evaluate("AA345").unwrap());
+        let rules = rules.with_wildcards("2");
+        assert_eq!(
+            rules.hand_type(&rules.cards("22345").unwrap()).name(),
+            "three of a kind"
+        );
+    }
+
+    #[test]
+    fn test_promote() {
+        let rules = Rules::part_2();
+        assert_eq!(
+            rules.promote(&rules.cards("KTJJT").unwrap()),
+            vec!['K', 'T', 'T', 'T', 'T']
+        );
+        assert_eq!(
+            rules.promote(&rules.cards("QJ2KA").unwrap()),
+            vec!['Q', 'A', '2', 'K', 'A']
+        );
+        assert_eq!(rules.promote(&rules.cards("JJJJJ").unwrap()), vec!['A'; 5]);
+    }
+
+    #[test]
+    fn test_poker_tie_break() {
+        let rules = Rules::part_1().with_tie_break(TieBreak::Poker);
+        assert!(rules.evaluate("2AAA2").unwrap() > rules.evaluate("KKK33").unwrap());
+        assert!(rules.evaluate("23K44").unwrap() < rules.evaluate("QQ234").unwrap());
+        assert_eq!(
+            rules.evaluate("QQ243").unwrap(),
+            rules.evaluate("QQ234").unwrap()
+        );
+        assert!(
+            Rules::part_1().evaluate("2AAA2").unwrap() < Rules::part_1().evaluate("KKK33").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_straights() {
+        let rules = Rules::part_1().with_straights(true);
+        assert_eq!(
+            rules.hand_type(&rules.cards("T9J8Q").unwrap()).name(),
+            "straight"
+        );
+        assert_eq!(
+            rules.hand_type(&rules.cards("T9J8K").unwrap()).name(),
+            "high card"
+        );
+        assert_eq!(
+            rules.hand_type(&rules.cards("23345").unwrap()).name(),
+            "one pair"
+        );
+        assert!(rules.evaluate("23456").unwrap() > rules.evaluate("AAA23").unwrap());
+        assert!(rules.evaluate("23456").unwrap() < rules.evaluate("22333").unwrap());
+
+        let rules = Rules::part_2().with_straights(true);
+        assert_eq!(
+            rules.hand_type(&rules.cards("T9J8Q").unwrap()).name(),
+            "straight"
+        );
+        assert_eq!(
+            rules.hand_type(&rules.cards("T9JKQ").unwrap()).name(),
+            "straight"
+        );
+        assert_eq!(
+            rules.hand_type(&rules.cards("T9J8J").unwrap()).name(),
+            "straight"
+        );
+    }
+
+    #[test]
+    fn test_suits() {
+        let suits = ["AKQJT".to_owned(), "98765432".to_owned()];
+        let rules = Rules::part_1().with_suits(&suits);
+        assert_eq!(
+            rules.hand_type(&rules.cards("AKQ2T").unwrap()).name(),
+            "high card"
+        );
+        assert_eq!(
+            rules.hand_type(&rules.cards("AKQAT").unwrap()).name(),
+            "flush"
+        );
+        assert_eq!(
+            rules.hand_type(&rules.cards("AAAKK").unwrap()).name(),
+            "full house"
+        );
+        assert_eq!(
+            rules.hand_type(&rules.cards("98762").unwrap()).name(),
+            "flush"
+        );
+
+        let rules = rules.with_straights(true);
+        assert_eq!(
+            rules.hand_type(&rules.cards("AKQJT").unwrap()).name(),
+            "straight flush"
+        );
+        assert_eq!(
+            rules.hand_type(&rules.cards("9876T").unwrap()).name(),
+            "straight"
+        );
+    }
+
+    #[test]
+    fn test_hand_sizes() {
+        let rules = Rules::part_2();
+        let hand_type = |cards| rules.hand_type(&rules.cards(cards).unwrap()).name();
+        assert_eq!(hand_type("A"), "high card");
+        assert_eq!(hand_type("KJ"), "one pair");
+        assert_eq!(hand_type("22334455"), "groups of 2+2+2+2");
+        assert_eq!(hand_type("2233445J"), "groups of 3+2+2");
+        assert!(rules.evaluate("223344").unwrap() > rules.evaluate("AA2345").unwrap());
+        assert!(rules.evaluate("KKK").unwrap() > rules.evaluate("AAK").unwrap());
+    }
+
+    #[test]
+    fn test_straights_and_flushes_of_small_hands() {
+        let suits = ["2468TQA".to_owned(), "3579JK".to_owned()];
+        let rules = Rules::part_1().with_straights(true).with_suits(&suits);
+        let name = |cards| rules.hand_type(&rules.cards(cards).unwrap()).name();
+        let evaluate = |cards| rules.evaluate(cards).unwrap();
+        assert_eq!(name("34"), "straight");
+        assert_eq!(name("26"), "flush");
+        assert_eq!(name("22"), "one pair");
+        assert!(evaluate("22") > evaluate("34"));
+        assert!(evaluate("22") > evaluate("26"));
+        assert_eq!(name("345"), "straight");
+        assert_eq!(name("268"), "flush");
+        assert_eq!(name("222"), "three of a kind");
+        assert!(evaluate("222") > evaluate("345"));
+        assert!(evaluate("222") > evaluate("268"));
+    }
+
+    #[test]
+    fn test_unsupported_hands() {
+        let rules = Rules::part_1();
+        assert_eq!(rules.evaluate(""), Err(HandError::EmptyHand));
+        assert_eq!(rules.evaluate("23456789T"), Err(HandError::TooManyCards(9)));
+        assert_eq!(rules.evaluate("2345X"), Err(HandError::UnknownCard('X')));
+        let rules = rules.with_ranking("abcdefghijklmnopq");
+        assert_eq!(rules.evaluate("abc"), Err(HandError::TooManyRanks(17)));
+    }
+}
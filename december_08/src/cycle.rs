@@ -0,0 +1,297 @@
+use crate::network::{Instructions, Movable, State};
+use num::integer::Integer;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, PartialEq)]
+pub enum CycleError {
+    /// No ghost starts, so there is no step at which they all stand on end nodes.
+    NoStarts,
+    /// The ghosts never stand on end nodes at the same step.
+    NoSolution,
+    /// The first step at which they do does not fit into 64 bits.
+    Overflow,
+}
+
+impl Display for CycleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CycleError::NoStarts => write!(f, "no node matches the starts"),
+            CycleError::NoSolution => write!(f, "the ghosts never reach end nodes together"),
+            CycleError::Overflow => write!(f, "the ghosts reach end nodes together too late"),
+        }
+    }
+}
+
+/// Path of a ghost through the network. The next move only depends on the node and on the
+/// position in the instructions, so the path loops once such a state repeats.
+#[derive(Debug, PartialEq)]
+pub struct Cycle {
+    /// Number of steps before the ghost enters the loop.
+    pre_period: u64,
+    /// Number of steps of the loop.
+    period: u64,
+    /// Steps at which the ghost stands on an end node, until the loop is completed once.
+    end_offsets: Vec<u64>,
+}
+
+impl Cycle {
+    pub fn detect(instructions: &Instructions, state: &mut State) -> Self {
+        // Step at which each (node, instruction index) state was first seen.
+        let mut seen = vec![u64::MAX; state.network.len() * instructions.len()];
+        let mut end_offsets = Vec::new();
+        let mut step = 0;
+        loop {
+            let index = step as usize % instructions.len();
+            let key = state.current_position as usize * instructions.len() + index;
+            if seen[key] != u64::MAX {
+                return Cycle {
+                    pre_period: seen[key],
+                    period: step - seen[key],
+                    end_offsets,
+                };
+            }
+            seen[key] = step;
+            if state.is_finished() {
+                end_offsets.push(step);
+            }
+            state.move_to_next(instructions.direction(index));
+            step += 1;
+        }
+    }
+
+    /// Whether the ghost stands on an end node after the given number of steps.
+    pub fn ends_at(&self, step: u64) -> bool {
+        let step = if step < self.pre_period {
+            step
+        } else {
+            self.pre_period + (step - self.pre_period) % self.period
+        };
+        self.end_offsets.contains(&step)
+    }
+
+    /// Residues modulo the period of the steps at which the ghost stands on an end node once
+    /// it is in the loop.
+    fn loop_residues(&self) -> impl Iterator<Item = u64> + '_ {
+        self.end_offsets
+            .iter()
+            .filter(|&&offset| offset >= self.pre_period)
+            .map(|offset| offset % self.period)
+    }
+}
+
+impl Display for Cycle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let end_offsets = self
+            .end_offsets
+            .iter()
+            .map(|offset| offset.to_string())
+            .collect::<Vec<String>>();
+        write!(
+            f,
+            "loops every {} steps after {}, ends at steps {}",
+            self.period,
+            self.pre_period,
+            if end_offsets.is_empty() {
+                "none".to_owned()
+            } else {
+                end_offsets.join(", ")
+            }
+        )
+    }
+}
+
+/// Solution of `x = a (mod n)` and `x = b (mod m)` as a residue modulo `lcm(n, m)`, if any.
+fn crt((a, n): (i128, i128), (b, m): (i128, i128)) -> Result<Option<(i128, i128)>, CycleError> {
+    let gcd = n.extended_gcd(&m);
+    if (b - a) % gcd.gcd != 0 {
+        return Ok(None);
+    }
+    let reduced_m = m / gcd.gcd;
+    let lcm = (n / gcd.gcd).checked_mul(m).ok_or(CycleError::Overflow)?;
+    let k = ((b - a) / gcd.gcd)
+        .rem_euclid(reduced_m)
+        .checked_mul(gcd.x.rem_euclid(reduced_m))
+        .ok_or(CycleError::Overflow)?
+        % reduced_m;
+    Ok(Some(((a + n * k).rem_euclid(lcm), lcm)))
+}
+
+/// First step, at least 1, at which every ghost stands on an end node.
+pub fn first_common_end(cycles: &[Cycle]) -> Result<u64, CycleError> {
+    if cycles.is_empty() {
+        return Err(CycleError::NoStarts);
+    }
+    // Before every ghost is in its loop, a common end is an end of some ghost's pre-period.
+    let before_loops = cycles
+        .iter()
+        .flat_map(|cycle| {
+            cycle
+                .end_offsets
+                .iter()
+                .copied()
+                .filter(|&offset| offset < cycle.pre_period)
+        })
+        .filter(|&step| step >= 1 && cycles.iter().all(|cycle| cycle.ends_at(step)))
+        .min()
+        .map(i128::from);
+
+    // Afterwards, it satisfies one of the congruences of every ghost.
+    let mut congruences = vec![(0i128, 1i128)];
+    for cycle in cycles {
+        let mut combined = Vec::new();
+        for &congruence in &congruences {
+            for residue in cycle.loop_residues() {
+                if let Some(solution) = crt(congruence, (residue as i128, cycle.period as i128))? {
+                    if !combined.contains(&solution) {
+                        combined.push(solution);
+                    }
+                }
+            }
+        }
+        congruences = combined;
+    }
+    let start = cycles
+        .iter()
+        .map(|cycle| cycle.pre_period)
+        .max()
+        .unwrap_or(0)
+        .max(1) as i128;
+    let in_loops = congruences
+        .iter()
+        .map(|&(residue, modulus)| start + (residue - start).rem_euclid(modulus))
+        .min();
+
+    match before_loops.into_iter().chain(in_loops).min() {
+        Some(step) => u64::try_from(step).map_err(|_| CycleError::Overflow),
+        None => Err(CycleError::NoSolution),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::Network;
+    use crate::predicate::NodePredicate;
+    use std::collections::HashMap;
+
+    fn network(edges: &[(&str, &str, &str)]) -> Network {
+        Network::new(
+            &edges
+                .iter()
+                .map(|&(node, left, right)| {
+                    (node.to_string(), (left.to_string(), right.to_string()))
+                })
+                .collect::<HashMap<String, (String, String)>>(),
+        )
+    }
+
+    fn cycle(instructions: &str, network: &Network, start: &str) -> Cycle {
+        let ends = NodePredicate::Suffix("Z".to_string()).select(network);
+        let mut state = State {
+            current_position: network.id(start).unwrap(),
+            ends: &ends,
+            network,
+        };
+        Cycle::detect(&Instructions::try_from(instructions).unwrap(), &mut state)
+    }
+
+    #[test]
+    fn test_detect() {
+        let network = network(&[
+            ("22A", "22B", "XXX"),
+            ("22B", "22C", "22C"),
+            ("22C", "22Z", "22Z"),
+            ("22Z", "22B", "22B"),
+            ("XXX", "XXX", "XXX"),
+        ]);
+        let cycle = cycle("LR", &network, "22A");
+        assert_eq!(
+            cycle,
+            Cycle {
+                pre_period: 1,
+                period: 6,
+                end_offsets: vec![3, 6]
+            }
+        );
+        assert!(!cycle.ends_at(0));
+        assert!(cycle.ends_at(9));
+        assert!(cycle.ends_at(12));
+        assert!(!cycle.ends_at(13));
+        assert_eq!(
+            cycle.to_string(),
+            "loops every 6 steps after 1, ends at steps 3, 6"
+        );
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt((2, 3), (3, 5)), Ok(Some((8, 15))));
+        assert_eq!(crt((1, 4), (3, 6)), Ok(Some((9, 12))));
+        assert_eq!(crt((0, 4), (1, 6)), Ok(None));
+    }
+
+    #[test]
+    fn test_first_common_end() {
+        let network = network(&[
+            ("11A", "11B", "XXX"),
+            ("11B", "XXX", "11Z"),
+            ("11Z", "11B", "XXX"),
+            ("22A", "22B", "XXX"),
+            ("22B", "22C", "22C"),
+            ("22C", "22Z", "22Z"),
+            ("22Z", "22B", "22B"),
+            ("XXX", "XXX", "XXX"),
+        ]);
+        let cycles = [cycle("LR", &network, "11A"), cycle("LR", &network, "22A")];
+        assert_eq!(first_common_end(&cycles), Ok(6));
+    }
+
+    #[test]
+    fn test_first_common_end_with_offsets() {
+        // Ends are reached at steps 3 (mod 4) and 1 (mod 3), first together at step 7, which
+        // no least common multiple of the first arrivals finds.
+        let network = network(&[
+            ("1A", "1B", "1B"),
+            ("1B", "1C", "1C"),
+            ("1C", "1Z", "1Z"),
+            ("1Z", "1A", "1A"),
+            ("2A", "2Z", "2Z"),
+            ("2Z", "2B", "2B"),
+            ("2B", "2A", "2A"),
+        ]);
+        let cycles = [cycle("L", &network, "1A"), cycle("L", &network, "2A")];
+        assert_eq!(first_common_end(&cycles), Ok(7));
+    }
+
+    #[test]
+    fn test_first_common_end_before_loops() {
+        // The first ghost only passes its end node once, before looping on XXX.
+        let network = network(&[
+            ("1A", "1Z", "1Z"),
+            ("1Z", "XXX", "XXX"),
+            ("XXX", "XXX", "XXX"),
+            ("2A", "2Z", "2Z"),
+            ("2Z", "2A", "2A"),
+        ]);
+        let cycles = [cycle("L", &network, "1A"), cycle("L", &network, "2A")];
+        assert_eq!(first_common_end(&cycles), Ok(1));
+    }
+
+    #[test]
+    fn test_first_common_end_without_starts() {
+        assert_eq!(first_common_end(&[]), Err(CycleError::NoStarts));
+    }
+
+    #[test]
+    fn test_no_common_end() {
+        let network = network(&[
+            ("1A", "1Z", "1Z"),
+            ("1Z", "1A", "1A"),
+            ("2A", "2B", "2B"),
+            ("2B", "2Z", "2Z"),
+            ("2Z", "2B", "2B"),
+        ]);
+        let cycles = [cycle("L", &network, "1A"), cycle("L", &network, "2A")];
+        assert_eq!(first_common_end(&cycles), Err(CycleError::NoSolution));
+    }
+}
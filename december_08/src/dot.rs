@@ -0,0 +1,173 @@
+use crate::cycle::Cycle;
+use crate::network::Network;
+
+const START_COLOR: &str = "palegreen";
+const END_COLOR: &str = "lightcoral";
+
+/// Nodes reachable from the start, in the order they are first reached.
+fn reachable(network: &Network, start: u32) -> Vec<u32> {
+    let mut visited = vec![false; network.len()];
+    let mut stack = vec![start];
+    let mut nodes = Vec::new();
+    while let Some(node) = stack.pop() {
+        if visited[node as usize] {
+            continue;
+        }
+        visited[node as usize] = true;
+        nodes.push(node);
+        stack.extend([network.next(node, 1), network.next(node, 0)]);
+    }
+    nodes
+}
+
+/// Attributes of a start node, annotated with the cycle of its path if it was detected.
+fn start_attributes(label: Option<String>, cycle: Option<&Cycle>) -> String {
+    let mut attributes = vec![
+        "style=filled".to_owned(),
+        format!("fillcolor={}", START_COLOR),
+    ];
+    if let Some(label) = label {
+        attributes.insert(0, "shape=box".to_owned());
+        attributes.push(format!("label=\"{}\"", label));
+    }
+    if let Some(cycle) = cycle {
+        attributes.push(format!("xlabel=\"{}\"", cycle));
+    }
+    attributes.join(", ")
+}
+
+/// Network in the DOT format of Graphviz, with the starts and ends highlighted. Each start is
+/// annotated with the cycle of its path when `cycles` holds one per start. When `collapse` is
+/// set, the nodes reachable from each start are drawn as that single start.
+pub fn to_dot(
+    network: &Network,
+    starts: &[u32],
+    is_end: impl Fn(u32) -> bool,
+    cycles: &[Cycle],
+    collapse: bool,
+) -> String {
+    let mut lines = vec!["digraph network {".to_owned()];
+    if collapse {
+        for (i, &start) in starts.iter().enumerate() {
+            let component = reachable(network, start);
+            let ends = component
+                .iter()
+                .filter(|&&node| is_end(node))
+                .map(|&node| network.name(node))
+                .collect::<Vec<&str>>();
+            let label = format!(
+                "{}\\n{} nodes\\n{}",
+                network.name(start),
+                component.len(),
+                if ends.is_empty() {
+                    "no end".to_owned()
+                } else {
+                    format!("ends {}", ends.join(", "))
+                }
+            );
+            lines.push(format!(
+                "    \"{}\" [{}];",
+                network.name(start),
+                start_attributes(Some(label), cycles.get(i))
+            ));
+        }
+    } else {
+        for (i, &start) in starts.iter().enumerate() {
+            lines.push(format!(
+                "    \"{}\" [{}];",
+                network.name(start),
+                start_attributes(None, cycles.get(i))
+            ));
+        }
+        for node in network.ids().filter(|&node| is_end(node)) {
+            lines.push(format!(
+                "    \"{}\" [style=filled, fillcolor={}];",
+                network.name(node),
+                END_COLOR
+            ));
+        }
+        for node in network.ids() {
+            let (left, right) = (network.next(node, 0), network.next(node, 1));
+            let edges = if left == right {
+                vec![(left, "LR")]
+            } else {
+                vec![(left, "L"), (right, "R")]
+            };
+            for (neighbour, label) in edges {
+                lines.push(format!(
+                    "    \"{}\" -> \"{}\" [label=\"{}\"];",
+                    network.name(node),
+                    network.name(neighbour),
+                    label
+                ));
+            }
+        }
+    }
+    lines.push("}".to_owned());
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn network() -> Network {
+        Network::new(&HashMap::from([
+            ("11A".to_string(), ("11B".to_string(), "XXX".to_string())),
+            ("11B".to_string(), ("XXX".to_string(), "11Z".to_string())),
+            ("11Z".to_string(), ("11B".to_string(), "XXX".to_string())),
+            ("22A".to_string(), ("22Z".to_string(), "22Z".to_string())),
+            ("22Z".to_string(), ("22A".to_string(), "22A".to_string())),
+            ("XXX".to_string(), ("XXX".to_string(), "XXX".to_string())),
+        ]))
+    }
+
+    #[test]
+    fn test_reachable() {
+        let network = network();
+        let names = |start| {
+            reachable(&network, network.id(start).unwrap())
+                .into_iter()
+                .map(|node| network.name(node))
+                .collect::<Vec<&str>>()
+        };
+        assert_eq!(names("11A"), vec!["11A", "11B", "XXX", "11Z"]);
+        assert_eq!(names("22Z"), vec!["22Z", "22A"]);
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let network = network();
+        let starts = [network.id("11A").unwrap(), network.id("22A").unwrap()];
+        let is_end = |node| network.name(node).ends_with('Z');
+        let dot = to_dot(&network, &starts, is_end, &[], false);
+        assert!(dot.starts_with("digraph network {\n"));
+        assert!(dot.ends_with("\n}"));
+        assert!(dot.contains("    \"11A\" [style=filled, fillcolor=palegreen];"));
+        assert!(dot.contains("    \"22Z\" [style=filled, fillcolor=lightcoral];"));
+        assert!(dot.contains("    \"11A\" -> \"11B\" [label=\"L\"];"));
+        assert!(dot.contains("    \"11A\" -> \"XXX\" [label=\"R\"];"));
+        assert!(dot.contains("    \"XXX\" -> \"XXX\" [label=\"LR\"];"));
+        assert_eq!(dot.matches("->").count(), 9);
+    }
+
+    #[test]
+    fn test_to_dot_collapsed() {
+        let network = network();
+        let starts = [network.id("11A").unwrap(), network.id("22A").unwrap()];
+        let is_end = |node| network.name(node).ends_with('Z');
+        assert_eq!(
+            to_dot(&network, &starts, is_end, &[], true),
+            [
+                "digraph network {",
+                "    \"11A\" [shape=box, style=filled, fillcolor=palegreen, \
+                 label=\"11A\\n4 nodes\\nends 11Z\"];",
+                "    \"22A\" [shape=box, style=filled, fillcolor=palegreen, \
+                 label=\"22A\\n2 nodes\\nends 22Z\"];",
+                "}",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -1,10 +1,18 @@
-use num::integer::lcm;
-use std::collections::{HashMap, VecDeque};
+mod cycle;
+mod dot;
+mod network;
+mod predicate;
+
+use crate::cycle::{first_common_end, Cycle, CycleError};
+use crate::dot::to_dot;
+use crate::network::{Instructions, InstructionsError, MacroSteps, Movable, Network, State};
+use crate::predicate::{NodePredicate, SelectionError};
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fs::read_to_string;
 use std::time::Instant;
 
-use clap::{Parser, ValueEnum};
+use clap::{Parser, Subcommand, ValueEnum};
 use regex::Regex;
 
 #[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
@@ -13,6 +21,19 @@ pub enum Tasks {
     Task2,
 }
 
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Print the network in the DOT format of Graphviz instead of solving the task
+    Dot {
+        /// Draw the nodes reachable from each start as that single start
+        #[arg(long)]
+        collapse: bool,
+        /// Annotate each start with the cycle of its path
+        #[arg(long)]
+        cycles: bool,
+    },
+}
+
 #[derive(Debug, Parser)]
 pub struct Cli {
     /// Input file to read
@@ -20,34 +41,17 @@ pub struct Cli {
     /// Number of lines to read
     #[structopt(short = 't')]
     task: Tasks,
-}
-
-trait Movable {
-    fn is_finished(&self) -> bool;
-    fn move_to_next(&mut self, instruction: char);
-}
-
-struct State<'a> {
-    current_position: &'a str,
-    end_suffix: &'a str,
-
-    nodes: &'a HashMap<String, (String, String)>,
-}
-
-impl<'a> Movable for State<'a> {
-    fn is_finished(&self) -> bool {
-        self.current_position.ends_with(&self.end_suffix)
-    }
-
-    fn move_to_next(&mut self, instruction: char) {
-        match instruction {
-            'L' => self.current_position = &self.nodes.get(self.current_position).unwrap().0,
-            'R' => self.current_position = &self.nodes.get(self.current_position).unwrap().1,
-            _ => {
-                panic!("Unknown instruction {}", instruction)
-            }
-        }
-    }
+    /// Print the node every start reaches after this number of steps instead of solving the task
+    #[arg(long)]
+    after: Option<u64>,
+    /// Start nodes as suffix:<S>, prefix:<P>, regex:<R> or list:<A,B>, overriding the task
+    #[arg(long)]
+    starts: Option<NodePredicate>,
+    /// End nodes as suffix:<S>, prefix:<P>, regex:<R> or list:<A,B>, overriding the task
+    #[arg(long)]
+    ends: Option<NodePredicate>,
+    #[command(subcommand)]
+    command: Option<Command>,
 }
 
 fn parse_file(input_file: &str) -> (String, HashMap<String, (String, String)>) {
@@ -70,58 +74,185 @@ fn parse_file(input_file: &str) -> (String, HashMap<String, (String, String)>) {
     (instructions, nodes)
 }
 
-fn compute_num_steps(instructions: String, state: &mut impl Movable) -> u64 {
-    let mut instruction_vec: VecDeque<char> = VecDeque::new();
+fn compute_num_steps(instructions: &Instructions, state: &mut impl Movable) -> u64 {
     let mut num_steps = 0;
 
-    while !state.is_finished() || !instruction_vec.is_empty() {
-        if instruction_vec.is_empty() {
-            let mut chars = instructions.clone().chars().into_iter().collect();
-            instruction_vec.append(&mut chars);
-        }
-        state.move_to_next(instruction_vec.pop_front().unwrap());
+    while !state.is_finished() || num_steps % instructions.len() != 0 {
+        state.move_to_next(instructions.direction(num_steps));
         num_steps += 1;
     }
-    num_steps
+    num_steps as u64
+}
+
+/// Predicates selecting the start and end nodes of the task.
+fn task_predicates(task: Tasks) -> (NodePredicate, NodePredicate) {
+    match task {
+        Tasks::Task1 => (
+            NodePredicate::List(vec!["AAA".to_string()]),
+            NodePredicate::List(vec!["ZZZ".to_string()]),
+        ),
+        Tasks::Task2 => (
+            NodePredicate::Suffix("A".to_string()),
+            NodePredicate::Suffix("Z".to_string()),
+        ),
+    }
 }
 
-fn task_1(input_file: &str) -> u64 {
+/// Network and instructions of the input file.
+fn load(input_file: &str) -> Result<(Network, Instructions), InstructionsError> {
     let (instructions, nodes) = parse_file(input_file);
+    Ok((
+        Network::new(&nodes),
+        Instructions::try_from(instructions.as_str())?,
+    ))
+}
+
+/// Number of steps from the only start to an end, with the end reached.
+fn task_1(
+    network: &Network,
+    instructions: &Instructions,
+    starts: &NodePredicate,
+    ends: &NodePredicate,
+) -> Result<(u64, String), SelectionError> {
+    let ends = ends.select(network);
     let mut state = State {
-        end_suffix: "ZZZ",
-        current_position: "AAA",
-        nodes: &nodes,
+        ends: &ends,
+        current_position: starts.select_one(network)?,
+        network,
     };
-    compute_num_steps(instructions, &mut state)
+    let num_steps = compute_num_steps(instructions, &mut state);
+    Ok((num_steps, state.reached_end().unwrap().to_string()))
 }
 
-fn task_2(input_file: &str) -> u64 {
-    let (instructions, nodes) = parse_file(input_file);
+fn task_2(
+    network: &Network,
+    instructions: &Instructions,
+    starts: &NodePredicate,
+    ends: &NodePredicate,
+) -> Result<u64, CycleError> {
+    let ends = ends.select(network);
+
+    let cycles: Vec<Cycle> = network
+        .ids()
+        .filter(|&node| starts.matches(network.name(node)))
+        .map(|node| {
+            let mut state = State {
+                ends: &ends,
+                current_position: node,
+                network,
+            };
+            Cycle::detect(instructions, &mut state)
+        })
+        .collect::<Vec<Cycle>>();
 
-    let mut states_vec: Vec<State> = nodes
-        .keys()
-        .into_iter()
-        .filter(|&node| node.ends_with("A"))
-        .map(|node| State {
-            end_suffix: "Z",
-            current_position: node,
-            nodes: &nodes,
+    first_common_end(&cycles)
+}
+
+/// Nodes reached by every start after the number of steps, as `start -> node`.
+fn positions_after(
+    network: &Network,
+    instructions: &Instructions,
+    starts: &NodePredicate,
+    num_steps: u64,
+) -> Vec<String> {
+    let macro_steps = MacroSteps::new(network, instructions);
+
+    network
+        .ids()
+        .filter(|&node| starts.matches(network.name(node)))
+        .map(|node| {
+            let position = macro_steps.position_after(node, num_steps);
+            format!("{} -> {}", network.name(node), network.name(position))
         })
-        .collect::<Vec<State>>();
+        .collect()
+}
+
+/// Network of the input in the DOT format, highlighting the starts and ends of the task.
+fn dot(
+    network: &Network,
+    instructions: &Instructions,
+    starts: &NodePredicate,
+    ends: &NodePredicate,
+    collapse: bool,
+    with_cycles: bool,
+) -> String {
+    let ends = ends.select(network);
 
-    states_vec
-        .iter_mut()
-        .map(|state| compute_num_steps(instructions.clone(), state))
-        .fold(1, lcm)
+    let starts = network
+        .ids()
+        .filter(|&node| starts.matches(network.name(node)))
+        .collect::<Vec<u32>>();
+    let cycles = if with_cycles {
+        starts
+            .iter()
+            .map(|&node| {
+                let mut state = State {
+                    ends: &ends,
+                    current_position: node,
+                    network,
+                };
+                Cycle::detect(instructions, &mut state)
+            })
+            .collect::<Vec<Cycle>>()
+    } else {
+        Vec::new()
+    };
+    to_dot(
+        network,
+        &starts,
+        |node| ends[node as usize],
+        &cycles,
+        collapse,
+    )
 }
 
 fn main() {
     let args = Cli::parse();
+    let (task_starts, task_ends) = task_predicates(args.task);
+    let starts = args.starts.unwrap_or(task_starts);
+    let ends = args.ends.unwrap_or(task_ends);
+    let (network, instructions) = match load(&args.input_file) {
+        Ok(loaded) => loaded,
+        Err(error) => {
+            eprintln!("invalid input: {}", error);
+            std::process::exit(1);
+        }
+    };
+
+    if let Some(Command::Dot { collapse, cycles }) = args.command {
+        println!(
+            "{}",
+            dot(&network, &instructions, &starts, &ends, collapse, cycles)
+        );
+        return;
+    }
+
+    if let Some(num_steps) = args.after {
+        for line in positions_after(&network, &instructions, &starts, num_steps) {
+            println!("{}", line);
+        }
+        return;
+    }
 
     let start = Instant::now();
     let result = match args.task {
-        Tasks::Task1 => task_1(&args.input_file),
-        Tasks::Task2 => task_2(&args.input_file),
+        Tasks::Task1 => match task_1(&network, &instructions, &starts, &ends) {
+            Ok((num_steps, end)) => {
+                println!("Reached end node {}", end);
+                num_steps
+            }
+            Err(error) => {
+                eprintln!("invalid start: {}", error);
+                std::process::exit(1);
+            }
+        },
+        Tasks::Task2 => match task_2(&network, &instructions, &starts, &ends) {
+            Ok(num_steps) => num_steps,
+            Err(error) => {
+                eprintln!("no solution: {}", error);
+                std::process::exit(1);
+            }
+        },
     };
     println!(
         "The task took {}ms to complete",
@@ -154,23 +285,58 @@ mod tests {
             ("BBB".to_string(), ("AAA".to_string(), "ZZZ".to_string())),
             ("ZZZ".to_string(), ("ZZZ".to_string(), "ZZZ".to_string())),
         ]);
-        let instructions = "LLR".to_string();
+        let network = Network::new(&nodes);
+        let instructions = Instructions::try_from("LLR").unwrap();
+        let ends = NodePredicate::List(vec!["ZZZ".to_string()]).select(&network);
         let mut state = State {
-            current_position: "AAA",
-            end_suffix: "ZZZ",
-            nodes: &nodes,
+            current_position: network.id("AAA").unwrap(),
+            ends: &ends,
+            network: &network,
+        };
+        assert_eq!(compute_num_steps(&instructions, &mut state), 6);
+        assert_eq!(state.reached_end(), Some("ZZZ"));
+    }
+
+    #[test]
+    fn test_compute_num_steps_with_multiple_ends() {
+        let nodes: HashMap<String, (String, String)> = HashMap::from([
+            ("AAA".to_string(), ("BBB".to_string(), "CCC".to_string())),
+            ("BBB".to_string(), ("YYY".to_string(), "ZZZ".to_string())),
+            ("CCC".to_string(), ("ZZZ".to_string(), "YYY".to_string())),
+            ("YYY".to_string(), ("YYY".to_string(), "YYY".to_string())),
+            ("ZZZ".to_string(), ("ZZZ".to_string(), "ZZZ".to_string())),
+        ]);
+        let network = Network::new(&nodes);
+        let ends = NodePredicate::Regex(Regex::new("^[YZ]+$").unwrap()).select(&network);
+        let reached_end = |instructions| {
+            let mut state = State {
+                current_position: network.id("AAA").unwrap(),
+                ends: &ends,
+                network: &network,
+            };
+            let instructions = Instructions::try_from(instructions).unwrap();
+            let num_steps = compute_num_steps(&instructions, &mut state);
+            (num_steps, state.reached_end().unwrap().to_string())
         };
-        assert_eq!(compute_num_steps(instructions, &mut state), 6);
+        assert_eq!(reached_end("LL"), (2, "YYY".to_string()));
+        assert_eq!(reached_end("RL"), (2, "ZZZ".to_string()));
     }
 
     #[test]
     fn test_task_1() {
-        assert_eq!(task_1("./inputs/input_test_1.txt"), 6);
-        assert_eq!(task_1("./inputs/input_test_2.txt"), 2);
+        let (starts, ends) = task_predicates(Tasks::Task1);
+        let num_steps = |input_file| {
+            let (network, instructions) = load(input_file).unwrap();
+            task_1(&network, &instructions, &starts, &ends).map(|(num_steps, _)| num_steps)
+        };
+        assert_eq!(num_steps("./inputs/input_test_1.txt"), Ok(6));
+        assert_eq!(num_steps("./inputs/input_test_2.txt"), Ok(2));
     }
 
     #[test]
     fn test_task_2() {
-        assert_eq!(task_2("./inputs/input_test_3.txt"), 6);
+        let (starts, ends) = task_predicates(Tasks::Task2);
+        let (network, instructions) = load("./inputs/input_test_3.txt").unwrap();
+        assert_eq!(task_2(&network, &instructions, &starts, &ends), Ok(6));
     }
 }
@@ -0,0 +1,258 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+/// Network with its nodes interned to ids, which index their names and their left and right
+/// neighbours.
+#[derive(Debug, PartialEq)]
+pub struct Network {
+    names: Vec<String>,
+    ids: HashMap<String, u32>,
+    neighbours: Vec<[u32; 2]>,
+}
+
+impl Network {
+    pub fn new(nodes: &HashMap<String, (String, String)>) -> Self {
+        let mut names = nodes.keys().cloned().collect::<Vec<String>>();
+        names.sort();
+        let ids = names
+            .iter()
+            .enumerate()
+            .map(|(id, name)| (name.clone(), id as u32))
+            .collect::<HashMap<String, u32>>();
+        let neighbours = names
+            .iter()
+            .map(|name| {
+                let (left, right) = &nodes[name];
+                [ids[left], ids[right]]
+            })
+            .collect();
+        Network {
+            names,
+            ids,
+            neighbours,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn ids(&self) -> impl Iterator<Item = u32> {
+        0..self.len() as u32
+    }
+
+    pub fn id(&self, name: &str) -> Option<u32> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: u32) -> &str {
+        &self.names[id as usize]
+    }
+
+    /// Neighbour of the node in the direction, 0 for left and 1 for right.
+    pub fn next(&self, id: u32, direction: usize) -> u32 {
+        self.neighbours[id as usize][direction]
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum InstructionsError {
+    Empty,
+    UnknownInstruction(char),
+}
+
+impl Display for InstructionsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InstructionsError::Empty => write!(f, "no instructions given"),
+            InstructionsError::UnknownInstruction(instruction) => {
+                write!(f, "unknown instruction '{}'", instruction)
+            }
+        }
+    }
+}
+
+/// Instructions packed into bits, set for `R`. There is at least one instruction, as they are
+/// repeated forever.
+#[derive(Debug, PartialEq)]
+pub struct Instructions {
+    bits: Vec<u64>,
+    len: usize,
+}
+
+impl TryFrom<&str> for Instructions {
+    type Error = InstructionsError;
+
+    fn try_from(instructions: &str) -> Result<Self, Self::Error> {
+        if instructions.is_empty() {
+            return Err(InstructionsError::Empty);
+        }
+        let mut bits = vec![0; instructions.len().div_ceil(64)];
+        for (i, instruction) in instructions.chars().enumerate() {
+            match instruction {
+                'L' => {}
+                'R' => bits[i / 64] |= 1 << (i % 64),
+                _ => return Err(InstructionsError::UnknownInstruction(instruction)),
+            }
+        }
+        Ok(Instructions {
+            bits,
+            len: instructions.len(),
+        })
+    }
+}
+
+impl Instructions {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Direction to follow at the step, repeating the instructions, 0 for left and 1 for right.
+    pub fn direction(&self, step: usize) -> usize {
+        let i = step % self.len;
+        ((self.bits[i / 64] >> (i % 64)) & 1) as usize
+    }
+}
+
+/// Tables of the node reached from every node after 2^k whole instruction cycles, to jump
+/// over billions of steps at once.
+pub struct MacroSteps<'a> {
+    network: &'a Network,
+    instructions: &'a Instructions,
+    levels: Vec<Vec<u32>>,
+}
+
+impl<'a> MacroSteps<'a> {
+    pub fn new(network: &'a Network, instructions: &'a Instructions) -> Self {
+        let cycle = network
+            .ids()
+            .map(|id| {
+                (0..instructions.len()).fold(id, |node, step| {
+                    network.next(node, instructions.direction(step))
+                })
+            })
+            .collect::<Vec<u32>>();
+        let mut levels = vec![cycle];
+        while levels.len() < u64::BITS as usize {
+            let last = levels.last().unwrap();
+            let next = last.iter().map(|&node| last[node as usize]).collect();
+            levels.push(next);
+        }
+        MacroSteps {
+            network,
+            instructions,
+            levels,
+        }
+    }
+
+    /// Node reached from the start after the number of steps.
+    pub fn position_after(&self, start: u32, num_steps: u64) -> u32 {
+        let cycle_len = self.instructions.len() as u64;
+        let (num_cycles, rest) = (num_steps / cycle_len, num_steps % cycle_len);
+        let node = self
+            .levels
+            .iter()
+            .enumerate()
+            .filter(|&(level, _)| (num_cycles >> level) & 1 == 1)
+            .fold(start, |node, (_, table)| table[node as usize]);
+        (0..rest as usize).fold(node, |node, step| {
+            self.network.next(node, self.instructions.direction(step))
+        })
+    }
+}
+
+pub trait Movable {
+    /// Name of the end node the position is on, if any.
+    fn reached_end(&self) -> Option<&str>;
+    fn move_to_next(&mut self, direction: usize);
+
+    fn is_finished(&self) -> bool {
+        self.reached_end().is_some()
+    }
+}
+
+pub struct State<'a> {
+    pub current_position: u32,
+    /// Whether each node is an end, indexed by node id.
+    pub ends: &'a [bool],
+
+    pub network: &'a Network,
+}
+
+impl<'a> Movable for State<'a> {
+    fn reached_end(&self) -> Option<&str> {
+        if self.ends[self.current_position as usize] {
+            Some(self.network.name(self.current_position))
+        } else {
+            None
+        }
+    }
+
+    fn move_to_next(&mut self, direction: usize) {
+        self.current_position = self.network.next(self.current_position, direction);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn network() -> Network {
+        Network::new(&HashMap::from([
+            ("AAA".to_string(), ("BBB".to_string(), "CCC".to_string())),
+            ("BBB".to_string(), ("CCC".to_string(), "AAA".to_string())),
+            ("CCC".to_string(), ("ZZZ".to_string(), "BBB".to_string())),
+            ("ZZZ".to_string(), ("AAA".to_string(), "ZZZ".to_string())),
+        ]))
+    }
+
+    #[test]
+    fn test_network() {
+        let network = network();
+        assert_eq!(network.len(), 4);
+        let id = network.id("BBB").unwrap();
+        assert_eq!(network.name(id), "BBB");
+        assert_eq!(network.name(network.next(id, 0)), "CCC");
+        assert_eq!(network.name(network.next(id, 1)), "AAA");
+        assert_eq!(network.id("XXX"), None);
+    }
+
+    #[test]
+    fn test_instructions() {
+        let text = "LR".repeat(40) + "RRL";
+        let instructions = Instructions::try_from(text.as_str()).unwrap();
+        assert_eq!(instructions.len(), 83);
+        for (step, instruction) in text.chars().chain(text.chars()).enumerate() {
+            let direction = if instruction == 'R' { 1 } else { 0 };
+            assert_eq!(instructions.direction(step), direction);
+        }
+    }
+
+    #[test]
+    fn test_invalid_instructions() {
+        assert_eq!(Instructions::try_from(""), Err(InstructionsError::Empty));
+        assert_eq!(
+            Instructions::try_from("LRX"),
+            Err(InstructionsError::UnknownInstruction('X'))
+        );
+    }
+
+    #[test]
+    fn test_position_after() {
+        let network = network();
+        let instructions = Instructions::try_from("LRRLL").unwrap();
+        let macro_steps = MacroSteps::new(&network, &instructions);
+        let start = network.id("AAA").unwrap();
+        let mut node = start;
+        for num_steps in 0..200 {
+            assert_eq!(macro_steps.position_after(start, num_steps), node);
+            node = network.next(node, instructions.direction(num_steps as usize));
+        }
+        // Within 4 instruction cycles every node enters a loop of at most 4 cycles, whose
+        // length divides 60 cycles or 300 steps.
+        assert_eq!(
+            macro_steps.position_after(start, 3_000_000_000 * 300 + 27),
+            macro_steps.position_after(start, 27)
+        );
+    }
+}
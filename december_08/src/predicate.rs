@@ -0,0 +1,183 @@
+use crate::network::Network;
+use regex::Regex;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Debug, PartialEq)]
+pub enum PredicateError {
+    UnknownKind(String),
+    InvalidRegex(String),
+}
+
+impl Display for PredicateError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PredicateError::UnknownKind(kind) => write!(
+                f,
+                "unknown predicate '{}', expected suffix, prefix, regex or list",
+                kind
+            ),
+            PredicateError::InvalidRegex(pattern) => write!(f, "invalid regex '{}'", pattern),
+        }
+    }
+}
+
+impl std::error::Error for PredicateError {}
+
+#[derive(Debug, PartialEq)]
+pub enum SelectionError {
+    NoMatch,
+    /// Names of the nodes matched when a single one is expected.
+    SeveralMatches(Vec<String>),
+}
+
+impl Display for SelectionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SelectionError::NoMatch => write!(f, "no node matches"),
+            SelectionError::SeveralMatches(names) => {
+                write!(f, "several nodes match: {}", names.join(", "))
+            }
+        }
+    }
+}
+
+/// Nodes selected by their name, written as `suffix:Z`, `prefix:A`, `regex:^.2.$` or
+/// `list:AAA,BBB`.
+#[derive(Debug, Clone)]
+pub enum NodePredicate {
+    Suffix(String),
+    Prefix(String),
+    Regex(Regex),
+    List(Vec<String>),
+}
+
+impl FromStr for NodePredicate {
+    type Err = PredicateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, argument) = s.split_once(':').unwrap_or((s, ""));
+        match kind {
+            "suffix" => Ok(NodePredicate::Suffix(argument.to_string())),
+            "prefix" => Ok(NodePredicate::Prefix(argument.to_string())),
+            "regex" => Regex::new(argument)
+                .map(NodePredicate::Regex)
+                .map_err(|_| PredicateError::InvalidRegex(argument.to_string())),
+            "list" => Ok(NodePredicate::List(
+                argument.split(',').map(|name| name.to_string()).collect(),
+            )),
+            _ => Err(PredicateError::UnknownKind(kind.to_string())),
+        }
+    }
+}
+
+impl NodePredicate {
+    pub fn matches(&self, name: &str) -> bool {
+        match self {
+            NodePredicate::Suffix(suffix) => name.ends_with(suffix.as_str()),
+            NodePredicate::Prefix(prefix) => name.starts_with(prefix.as_str()),
+            NodePredicate::Regex(regex) => regex.is_match(name),
+            NodePredicate::List(names) => names.iter().any(|n| n == name),
+        }
+    }
+
+    /// Whether each node of the network is selected, indexed by node id.
+    pub fn select(&self, network: &Network) -> Vec<bool> {
+        match self {
+            NodePredicate::List(names) => {
+                let mut selected = vec![false; network.len()];
+                for node in names.iter().filter_map(|name| network.id(name)) {
+                    selected[node as usize] = true;
+                }
+                selected
+            }
+            _ => network
+                .ids()
+                .map(|node| self.matches(network.name(node)))
+                .collect(),
+        }
+    }
+
+    /// Id of the only node of the network which is selected.
+    pub fn select_one(&self, network: &Network) -> Result<u32, SelectionError> {
+        let nodes = network
+            .ids()
+            .filter(|&node| self.matches(network.name(node)))
+            .collect::<Vec<u32>>();
+        match nodes[..] {
+            [] => Err(SelectionError::NoMatch),
+            [node] => Ok(node),
+            _ => Err(SelectionError::SeveralMatches(
+                nodes
+                    .iter()
+                    .map(|&node| network.name(node).to_string())
+                    .collect(),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_matches() {
+        let matches =
+            |predicate: &str, name| NodePredicate::from_str(predicate).unwrap().matches(name);
+        assert!(matches("suffix:Z", "11Z"));
+        assert!(!matches("suffix:Z", "Z11"));
+        assert!(matches("prefix:22", "22A"));
+        assert!(!matches("prefix:22", "11A"));
+        assert!(matches("regex:^.1[AB]$", "11B"));
+        assert!(!matches("regex:^.1[AB]$", "11Z"));
+        assert!(matches("list:AAA,ZZZ", "ZZZ"));
+        assert!(!matches("list:AAA,ZZZ", "ZZ"));
+    }
+
+    #[test]
+    fn test_select() {
+        let network = Network::new(&HashMap::from([
+            ("AAA".to_string(), ("ZZZ".to_string(), "ZZZ".to_string())),
+            ("ZZZ".to_string(), ("AAA".to_string(), "ZZZ".to_string())),
+        ]));
+        let select = |predicate| NodePredicate::from_str(predicate).unwrap().select(&network);
+        assert_eq!(select("list:ZZZ,XXX"), vec![false, true]);
+        assert_eq!(select("prefix:A"), vec![true, false]);
+    }
+
+    #[test]
+    fn test_select_one() {
+        let network = Network::new(&HashMap::from([
+            ("AAA".to_string(), ("ZZZ".to_string(), "ZZZ".to_string())),
+            ("ZZZ".to_string(), ("AAA".to_string(), "ZZZ".to_string())),
+        ]));
+        let select_one = |predicate| {
+            NodePredicate::from_str(predicate)
+                .unwrap()
+                .select_one(&network)
+        };
+        assert_eq!(select_one("list:ZZZ"), Ok(network.id("ZZZ").unwrap()));
+        assert_eq!(select_one("suffix:B"), Err(SelectionError::NoMatch));
+        assert_eq!(
+            select_one("regex:^[AZ]+$"),
+            Err(SelectionError::SeveralMatches(vec![
+                "AAA".to_string(),
+                "ZZZ".to_string()
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_invalid_predicates() {
+        assert_eq!(
+            NodePredicate::from_str("postfix:Z").unwrap_err(),
+            PredicateError::UnknownKind("postfix".to_string())
+        );
+        assert_eq!(
+            NodePredicate::from_str("regex:(A").unwrap_err(),
+            PredicateError::InvalidRegex("(A".to_string())
+        );
+    }
+}
@@ -0,0 +1,221 @@
+use num::{BigInt, CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, FromPrimitive, One, Zero};
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, PartialEq)]
+pub enum FitError {
+    /// The differences of the sequence never become all zeros.
+    NotPolynomial,
+}
+
+impl Display for FitError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FitError::NotPolynomial => write!(f, "not a polynomial"),
+        }
+    }
+}
+
+/// Integer type the extrapolation can be computed in, detecting overflows.
+pub trait Value:
+    Clone
+    + Zero
+    + One
+    + CheckedAdd
+    + CheckedSub
+    + CheckedMul
+    + CheckedDiv
+    + FromPrimitive
+    + From<i64>
+    + Into<BigInt>
+{
+}
+
+impl<T> Value for T where
+    T: Clone
+        + Zero
+        + One
+        + CheckedAdd
+        + CheckedSub
+        + CheckedMul
+        + CheckedDiv
+        + FromPrimitive
+        + From<i64>
+        + Into<BigInt>
+{
+}
+
+/// First and last values of a row and of each of its successive differences, down to the
+/// first differences which are all zeros, excluded, or to a single value.
+pub struct Diagonals<T> {
+    firsts: Vec<T>,
+    lasts: Vec<T>,
+    /// Whether the differences end with all zeros, or the row is a single value and so a
+    /// constant. They end with a single value instead when the row does not follow a
+    /// polynomial of a degree lower than its length minus one.
+    reaches_zeros: bool,
+}
+
+impl<T> Diagonals<T> {
+    pub fn firsts(&self) -> &[T] {
+        &self.firsts
+    }
+
+    pub fn reaches_zeros(&self) -> bool {
+        self.reaches_zeros
+    }
+}
+
+/// Diagonals of the difference table of the row, computed in place, or `None` if the
+/// differences overflow.
+pub fn diagonals<T: Value>(row: &[i64]) -> Option<Diagonals<T>> {
+    let mut values = row.iter().map(|&value| T::from(value)).collect::<Vec<T>>();
+    let (mut firsts, mut lasts) = (Vec::new(), Vec::new());
+    let mut len = values.len();
+    while len > 0 && !values[..len].iter().all(Zero::is_zero) {
+        firsts.push(values[0].clone());
+        lasts.push(values[len - 1].clone());
+        for i in 0..len - 1 {
+            values[i] = values[i + 1].checked_sub(&values[i])?;
+        }
+        len -= 1;
+    }
+    Some(Diagonals {
+        firsts,
+        lasts,
+        reaches_zeros: len > 0 || row.len() == 1,
+    })
+}
+
+/// Values `num_steps` positions before the first and after the last value of the row, with
+/// whether the differences reach all zeros, or `None` if they overflow. With `d_i` the
+/// differences of order `i`, the value before is the sum of `(-1)^i C(k + i - 1, i) d_i` at the
+/// first position and the value after the sum of `C(k + i - 1, i) d_i` at the last position.
+fn extrapolate_in<T: Value>(row: &[i64], num_steps: u64) -> Option<(T, T, bool)> {
+    let Diagonals {
+        firsts,
+        lasts,
+        reaches_zeros,
+    } = diagonals::<T>(row)?;
+    let k = T::from_u64(num_steps)?;
+    let mut binomial = T::one();
+    let (mut before, mut after) = (T::zero(), T::zero());
+    for (i, (first, last)) in firsts.iter().zip(&lasts).enumerate() {
+        if i > 0 {
+            let order = T::from_usize(i)?;
+            binomial = binomial
+                .checked_mul(&k.checked_add(&order)?.checked_sub(&T::one())?)?
+                .checked_div(&order)?;
+        }
+        let term = first.checked_mul(&binomial)?;
+        before = if i % 2 == 0 {
+            before.checked_add(&term)?
+        } else {
+            before.checked_sub(&term)?
+        };
+        after = after.checked_add(&last.checked_mul(&binomial)?)?;
+    }
+    Some((before, after, reaches_zeros))
+}
+
+/// Values extrapolated before the first and after the last value of a row.
+#[derive(Debug, PartialEq)]
+pub struct Extrapolation {
+    pub before: BigInt,
+    pub after: BigInt,
+    /// Set when the differences of the row never become all zeros. The values then follow the
+    /// polynomial of degree `n - 1` through the `n` values of the row.
+    pub warning: Option<FitError>,
+}
+
+/// Values `num_steps` positions before the first and after the last value of the row, computed
+/// in `i64` and falling back to `i128` and then to big integers on overflow.
+pub fn extrapolate(row: &[i64], num_steps: u64) -> Extrapolation {
+    let (before, after, reaches_zeros) = match extrapolate_in::<i64>(row, num_steps) {
+        Some((before, after, reaches_zeros)) => (before.into(), after.into(), reaches_zeros),
+        None => match extrapolate_in::<i128>(row, num_steps) {
+            Some((before, after, reaches_zeros)) => (before.into(), after.into(), reaches_zeros),
+            None => extrapolate_in::<BigInt>(row, num_steps).unwrap(),
+        },
+    };
+    Extrapolation {
+        before,
+        after,
+        warning: (!reaches_zeros).then_some(FitError::NotPolynomial),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(before: i128, after: i128) -> Extrapolation {
+        Extrapolation {
+            before: BigInt::from(before),
+            after: BigInt::from(after),
+            warning: None,
+        }
+    }
+
+    #[test]
+    fn test_diagonals() {
+        let table = diagonals::<i64>(&[10, 13, 16, 21, 30, 45]).unwrap();
+        assert_eq!(table.firsts, vec![10, 3, 0, 2]);
+        assert_eq!(table.lasts, vec![45, 15, 6, 2]);
+        assert!(table.reaches_zeros());
+        assert!(!diagonals::<i64>(&[1, 2, 4, 8]).unwrap().reaches_zeros());
+        assert!(diagonals::<i64>(&[7]).unwrap().reaches_zeros());
+        assert!(!diagonals::<i64>(&[]).unwrap().reaches_zeros());
+    }
+
+    #[test]
+    fn test_extrapolate() {
+        let row = [1, 3, 6, 10, 15, 21];
+        assert_eq!(extrapolate(&row, 0), big(1, 21));
+        assert_eq!(extrapolate(&row, 1), big(0, 28));
+        assert_eq!(extrapolate(&row, 2), big(0, 36));
+        assert_eq!(extrapolate(&row, 3), big(1, 45));
+        assert_eq!(extrapolate(&[5], 10), big(5, 5));
+        assert_eq!(extrapolate(&[0, 0, 0], 10), big(0, 0));
+    }
+
+    #[test]
+    fn test_extrapolate_not_polynomial() {
+        let not_polynomial = |before, after| Extrapolation {
+            warning: Some(FitError::NotPolynomial),
+            ..big(before, after)
+        };
+        // The cubic through 1, 2, 4, 8 continues with 15 and comes from 0.
+        assert_eq!(extrapolate(&[1, 2, 4, 8], 1), not_polynomial(0, 15));
+        assert_eq!(extrapolate(&[1, 2], 1), not_polynomial(0, 3));
+        assert_eq!(extrapolate(&[], 1), not_polynomial(0, 0));
+    }
+
+    #[test]
+    fn test_extrapolate_far() {
+        // Squares, from 0 to 25.
+        let row = [0, 1, 4, 9, 16, 25];
+        let num_steps = 1_000_000_000_000;
+        let expected = (num_steps as i128).pow(2);
+        assert_eq!(
+            extrapolate(&row, num_steps),
+            big(expected, (num_steps as i128 + 5).pow(2))
+        );
+    }
+
+    #[test]
+    fn test_overflow_fallbacks() {
+        let row = [i64::MAX - 2, i64::MAX - 1, i64::MAX];
+        assert!(extrapolate_in::<i64>(&row, 1).is_none());
+        assert_eq!(
+            extrapolate(&row, 1),
+            big(i64::MAX as i128 - 3, i64::MAX as i128 + 1)
+        );
+
+        // Powers of 12 grow beyond i128 when extrapolated far enough.
+        let row = (0..20).map(|i: i64| i.pow(12)).collect::<Vec<i64>>();
+        assert!(extrapolate_in::<i128>(&row, 1 << 40).is_none());
+        let extrapolation = extrapolate(&row, 1 << 40);
+        assert_eq!(extrapolation.after, BigInt::from(19 + (1i64 << 40)).pow(12));
+        assert_eq!(extrapolation.warning, None);
+    }
+}
@@ -1,5 +1,10 @@
+mod extrapolation;
+mod polynomial;
+
+use crate::extrapolation::{extrapolate, Extrapolation};
+use crate::polynomial::Polynomial;
 use clap::{Parser, ValueEnum};
-use ndarray::{s, Array1};
+use num::BigInt;
 use std::fs::read_to_string;
 use std::time::Instant;
 
@@ -9,8 +14,6 @@ pub enum Tasks {
     Task2,
 }
 
-type Row = Array1<i64>;
-
 #[derive(Debug, Parser)]
 pub struct Cli {
     /// Input file to read
@@ -18,45 +21,100 @@ pub struct Cli {
     /// Number of lines to read
     #[structopt(short = 't')]
     task: Tasks,
+    /// Number of values to extrapolate beyond the ends of each sequence
+    #[arg(long, default_value_t = 1)]
+    steps: u64,
+    /// Print the polynomial fitted to each sequence instead of solving the task
+    #[arg(long)]
+    fit: bool,
+    /// Position at which to evaluate the fitted polynomials, 0 being the first value
+    #[arg(long, allow_hyphen_values = true, requires = "fit")]
+    at: Option<i64>,
 }
 
-fn line_to_array(line: &str) -> Row {
+fn parse_line(line: &str) -> Vec<i64> {
     line.split_whitespace()
         .map(|e| e.parse().unwrap())
-        .collect::<Row>()
+        .collect::<Vec<i64>>()
 }
 
-fn find_prediction(row: Row) -> (i64, i64) {
-    let diff: Row = &row.slice(s![1..]) - &row.slice(s![..-1]);
-    if diff == Row::zeros(diff.len()) {
-        return (row[0], row[row.len() - 1]);
-    } else {
-        let (first_prediction, last_prediction) = find_prediction(diff);
-        (row[0] - first_prediction, row[row.len() - 1] + last_prediction)
-    }
+fn find_prediction(row: &[i64], num_steps: u64) -> Extrapolation {
+    extrapolate(row, num_steps)
 }
 
-fn task_1(input_file: &str) -> i64 {
+/// Sum of the values extrapolated from every sequence of the input, the one before the first
+/// value if `backwards` is set. Sequences which are not polynomials are extrapolated with a
+/// warning.
+fn sum_predictions(input_file: &str, num_steps: u64, backwards: bool) -> BigInt {
     let content = read_to_string(input_file).unwrap();
     let lines = content.lines();
 
-     lines.map(line_to_array).map(|row| find_prediction(row).1).sum()
+    lines
+        .map(|line| {
+            let row = parse_line(line);
+            let extrapolation = find_prediction(&row, num_steps);
+            if let Some(warning) = extrapolation.warning {
+                eprintln!(
+                    "warning: {}: {}, extrapolating with degree {}",
+                    line,
+                    warning,
+                    row.len().saturating_sub(1)
+                );
+            }
+            if backwards {
+                extrapolation.before
+            } else {
+                extrapolation.after
+            }
+        })
+        .sum()
+}
+
+fn task_1(input_file: &str, num_steps: u64) -> BigInt {
+    sum_predictions(input_file, num_steps, false)
+}
+
+fn task_2(input_file: &str, num_steps: u64) -> BigInt {
+    sum_predictions(input_file, num_steps, true)
 }
 
-fn task_2(input_file: &str) -> i64 {
+/// Polynomial fitted to each sequence with its degree, and its value at the position if any.
+fn fit(input_file: &str, at: Option<i64>) -> Vec<String> {
     let content = read_to_string(input_file).unwrap();
-    let lines = content.lines();
 
-    lines.map(line_to_array).map(|row| find_prediction(row).0).sum()
+    content
+        .lines()
+        .map(|line| {
+            let row = parse_line(line);
+            match Polynomial::fit(&row) {
+                Ok(polynomial) => {
+                    let mut description =
+                        format!("{}: degree {}, {}", line, polynomial.degree(), polynomial);
+                    if let Some(x) = at {
+                        description += &format!(", {} at {}", polynomial.evaluate(x), x);
+                    }
+                    description
+                }
+                Err(error) => format!("{}: {}", line, error),
+            }
+        })
+        .collect()
 }
 
 fn main() {
     let args = Cli::parse();
 
+    if args.fit {
+        for line in fit(&args.input_file, args.at) {
+            println!("{}", line);
+        }
+        return;
+    }
+
     let start = Instant::now();
     let result = match args.task {
-        Tasks::Task1 => task_1(&args.input_file),
-        Tasks::Task2 => task_2(&args.input_file),
+        Tasks::Task1 => task_1(&args.input_file, args.steps),
+        Tasks::Task2 => task_2(&args.input_file, args.steps),
     };
     println!(
         "The task took {}ms to complete",
@@ -68,17 +126,31 @@ fn main() {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use ndarray::array;
+    use crate::extrapolation::FitError;
 
     #[test]
     fn test_find_prediction() {
-        assert_eq!(find_prediction(array![0, 3, 6, 9, 12, 15]), (-3, 18));
-        assert_eq!(find_prediction(array![1, 3, 6, 10, 15, 21]), (0, 28));
-        assert_eq!(find_prediction(array![10, 13, 16, 21, 30, 45]), (5, 68));
+        let prediction = |row: &[i64]| {
+            let Extrapolation { before, after, .. } = find_prediction(row, 1);
+            (
+                i64::try_from(before).unwrap(),
+                i64::try_from(after).unwrap(),
+            )
+        };
+        assert_eq!(prediction(&[0, 3, 6, 9, 12, 15]), (-3, 18));
+        assert_eq!(prediction(&[1, 3, 6, 10, 15, 21]), (0, 28));
+        assert_eq!(prediction(&[10, 13, 16, 21, 30, 45]), (5, 68));
     }
 
     #[test]
     fn test_task_1() {
-        assert_eq!(task_1("./inputs/input_test.txt"), 114);
+        assert_eq!(task_1("./inputs/input_test.txt", 1), BigInt::from(114));
+    }
+
+    #[test]
+    fn test_find_prediction_not_polynomial() {
+        let extrapolation = find_prediction(&[1, 2, 4, 8], 1);
+        assert_eq!(extrapolation.after, BigInt::from(15));
+        assert_eq!(extrapolation.warning, Some(FitError::NotPolynomial));
     }
 }
@@ -0,0 +1,139 @@
+use crate::extrapolation::{diagonals, FitError};
+use num::{BigInt, BigRational, One, Signed, Zero};
+use std::fmt::{Display, Formatter};
+
+/// Polynomial with exact rational coefficients, from the constant one.
+#[derive(Debug, PartialEq)]
+pub struct Polynomial {
+    coefficients: Vec<BigRational>,
+}
+
+impl Polynomial {
+    /// Polynomial taking the values of the sequence at 0, 1, 2..., from the Newton forward
+    /// differences `d_i` at 0: the sum of `d_i x (x - 1) ... (x - i + 1) / i!`.
+    pub fn fit(row: &[i64]) -> Result<Self, FitError> {
+        let diagonals = diagonals::<BigInt>(row).unwrap();
+        if !diagonals.reaches_zeros() {
+            return Err(FitError::NotPolynomial);
+        }
+        let mut coefficients: Vec<BigRational> = Vec::new();
+        // Coefficients of x (x - 1) ... (x - i + 1) / i!.
+        let mut basis = vec![BigRational::one()];
+        for (i, difference) in diagonals.firsts().iter().enumerate() {
+            if i > 0 {
+                let shift = BigRational::from_integer((i - 1).into());
+                let order = BigRational::from_integer(i.into());
+                let mut next = vec![BigRational::zero(); basis.len() + 1];
+                for (power, coefficient) in basis.iter().enumerate() {
+                    next[power + 1] += coefficient;
+                    next[power] -= coefficient * &shift;
+                }
+                basis = next
+                    .into_iter()
+                    .map(|coefficient| coefficient / &order)
+                    .collect();
+            }
+            coefficients.resize(basis.len(), BigRational::zero());
+            for (power, coefficient) in basis.iter().enumerate() {
+                coefficients[power] += coefficient * BigRational::from_integer(difference.clone());
+            }
+        }
+        while coefficients.last().is_some_and(Zero::is_zero) {
+            coefficients.pop();
+        }
+        Ok(Polynomial { coefficients })
+    }
+
+    /// Degree of the polynomial, 0 for the zero polynomial.
+    pub fn degree(&self) -> usize {
+        self.coefficients.len().saturating_sub(1)
+    }
+
+    pub fn evaluate(&self, x: i64) -> BigRational {
+        let x = BigRational::from_integer(x.into());
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(BigRational::zero(), |value, coefficient| {
+                value * &x + coefficient
+            })
+    }
+}
+
+impl Display for Polynomial {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let terms = self
+            .coefficients
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, coefficient)| !coefficient.is_zero())
+            .collect::<Vec<(usize, &BigRational)>>();
+        if terms.is_empty() {
+            return write!(f, "0");
+        }
+        for (i, &(power, coefficient)) in terms.iter().enumerate() {
+            let sign = match (i, coefficient.is_negative()) {
+                (0, false) => "",
+                (0, true) => "-",
+                (_, false) => " + ",
+                (_, true) => " - ",
+            };
+            let magnitude = coefficient.abs();
+            let magnitude = if magnitude.is_one() && power > 0 {
+                String::new()
+            } else {
+                magnitude.to_string()
+            };
+            let variable = match power {
+                0 => String::new(),
+                1 => "x".to_owned(),
+                _ => format!("x^{}", power),
+            };
+            write!(f, "{}{}{}", sign, magnitude, variable)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit() {
+        let fit = |row: &[i64]| Polynomial::fit(row).unwrap();
+        assert_eq!(fit(&[0, 3, 6, 9, 12, 15]).to_string(), "3x");
+        assert_eq!(fit(&[1, 3, 6, 10, 15, 21]).to_string(), "1/2x^2 + 3/2x + 1");
+        assert_eq!(
+            fit(&[10, 13, 16, 21, 30, 45]).to_string(),
+            "1/3x^3 - x^2 + 11/3x + 10"
+        );
+        assert_eq!(fit(&[-4, -4, -4]).to_string(), "-4");
+        assert_eq!(fit(&[0, 0]).to_string(), "0");
+        assert_eq!(fit(&[1, 3, 6, 10, 15, 21]).degree(), 2);
+        assert_eq!(fit(&[0, 0]).degree(), 0);
+        assert_eq!(fit(&[7]).to_string(), "7");
+        assert_eq!(fit(&[7]).degree(), 0);
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let polynomial = Polynomial::fit(&[10, 13, 16, 21, 30, 45]).unwrap();
+        let values = (-1..=6)
+            .map(|x| polynomial.evaluate(x))
+            .collect::<Vec<BigRational>>();
+        let expected =
+            [5, 10, 13, 16, 21, 30, 45, 68].map(|value| BigRational::from_integer(value.into()));
+        assert_eq!(values, expected);
+        let half = Polynomial::fit(&[0, 1, 3, 6]).unwrap();
+        assert_eq!(half.to_string(), "1/2x^2 + 1/2x");
+        assert_eq!(half.evaluate(-2), BigRational::one());
+    }
+
+    #[test]
+    fn test_not_polynomial() {
+        assert_eq!(Polynomial::fit(&[1, 2, 4, 8]), Err(FitError::NotPolynomial));
+        assert_eq!(Polynomial::fit(&[]), Err(FitError::NotPolynomial));
+    }
+}
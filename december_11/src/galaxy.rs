@@ -1,4 +1,27 @@
-use std::fs::read_to_string;
+use clap::ValueEnum;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+
+/// How the distance between two galaxies is measured.
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Metric {
+    /// Sum of the row and column distances
+    Manhattan,
+    /// Largest of the row and column distances
+    Chebyshev,
+    /// Straight line distance
+    Euclidean,
+}
+
+impl Metric {
+    pub fn distance(self, galaxy: &Galaxy, other: &Galaxy) -> f64 {
+        match self {
+            Metric::Manhattan => galaxy.distance(other) as f64,
+            Metric::Chebyshev => galaxy.chebyshev_distance(other) as f64,
+            Metric::Euclidean => galaxy.euclidean_distance(other),
+        }
+    }
+}
 
 #[derive(Eq, PartialEq, Debug)]
 pub struct Galaxy {
@@ -14,104 +37,389 @@ impl Galaxy {
         let col_dist = ((self.col as i64) - (other.col as i64)).abs();
         (row_dist + col_dist) as u64
     }
+
+    pub fn id(&self) -> i64 {
+        self.id
+    }
+
+    pub fn chebyshev_distance(&self, other: &Galaxy) -> u64 {
+        let row_dist = self.row.abs_diff(other.row);
+        let col_dist = self.col.abs_diff(other.col);
+        row_dist.max(col_dist) as u64
+    }
+
+    pub fn euclidean_distance(&self, other: &Galaxy) -> f64 {
+        let row_dist = self.row.abs_diff(other.row) as f64;
+        let col_dist = self.col.abs_diff(other.col) as f64;
+        row_dist.hypot(col_dist)
+    }
+}
+
+/// Distance between the galaxies with the given ids, or `None` if one of them does not exist.
+pub fn distance_between(
+    galaxies: &[Galaxy],
+    id: i64,
+    other_id: i64,
+    metric: Metric,
+) -> Option<f64> {
+    let find = |id| galaxies.iter().find(|galaxy| galaxy.id == id);
+    Some(metric.distance(find(id)?, find(other_id)?))
 }
 
-pub fn find_expansion_offsets_rows(map: &Vec<Vec<char>>, multiplier: usize) -> Vec<usize> {
-    let mut expansions: Vec<usize> = Vec::new();
-    let mut i = 0;
-    for row in map.iter() {
-        expansions.push(i);
-        if !row.contains(&'#') {
-            i += multiplier;
-        } else {
-            i += 1;
+/// Nearest other galaxy of every galaxy with its distance, `None` if it is the only galaxy.
+pub fn nearest_neighbours(galaxies: &[Galaxy], metric: Metric) -> Vec<Option<(&Galaxy, f64)>> {
+    galaxies
+        .iter()
+        .map(|galaxy| {
+            galaxies
+                .iter()
+                .filter(|other| other.id != galaxy.id)
+                .map(|other| (other, metric.distance(galaxy, other)))
+                .min_by(|(_, distance), (_, other_distance)| distance.total_cmp(other_distance))
+        })
+        .collect()
+}
+
+/// Sum of the distances between all pairs of galaxies, comparing every pair.
+pub fn sum_distances_brute_force(galaxies: &[Galaxy]) -> u128 {
+    let mut distances = 0;
+    for i in 0..galaxies.len() {
+        for j in (i + 1)..galaxies.len() {
+            distances += galaxies[i].distance(&galaxies[j]) as u128;
         }
     }
-    expansions
+    distances
 }
 
-pub fn find_expansion_offsets_columns(map: &Vec<Vec<char>>, multiplier: usize) -> Vec<usize> {
-    let mut expansions: Vec<usize> = Vec::new();
-    let nrows = map.len();
-    let ncols = map[0].len();
+/// Sum of the distances between all values, in O(n log n): once sorted, each value is
+/// farther than the ones before it by its own value times their number minus their sum. The
+/// sums are kept in `u128`, as the distances of expanded universes quickly add up beyond
+/// `u64`.
+fn sum_axis_distances(mut values: Vec<usize>) -> u128 {
+    values.sort_unstable();
+    let mut prefix_sum: u128 = 0;
+    let mut distances = 0;
+    for (i, &value) in values.iter().enumerate() {
+        distances += value as u128 * i as u128 - prefix_sum;
+        prefix_sum += value as u128;
+    }
+    distances
+}
 
-    let mut i = 0;
-    for col in 0..ncols {
-        let mut contains_galaxy = false;
-        for row in 0..nrows {
-            if map[row][col] == '#' {
-                contains_galaxy = true;
-                break;
-            }
-        }
-        expansions.push(i);
-        if !contains_galaxy {
-            i += multiplier;
-        } else {
-            i += 1;
+/// Sum of the distances between all pairs of galaxies, summing the Manhattan distance of
+/// each axis separately over sorted coordinates.
+pub fn sum_distances(galaxies: &[Galaxy]) -> u128 {
+    sum_axis_distances(galaxies.iter().map(|galaxy| galaxy.row).collect())
+        + sum_axis_distances(galaxies.iter().map(|galaxy| galaxy.col).collect())
+}
+
+/// Sum of the Chebyshev distances between all pairs of galaxies. In coordinates rotated to
+/// `row + col` and `row - col`, the Manhattan distance is twice the Chebyshev one.
+pub fn sum_chebyshev_distances(galaxies: &[Galaxy]) -> u128 {
+    let max_col = galaxies.iter().map(|galaxy| galaxy.col).max().unwrap_or(0);
+    let sum = sum_axis_distances(
+        galaxies
+            .iter()
+            .map(|galaxy| galaxy.row + galaxy.col)
+            .collect(),
+    ) + sum_axis_distances(
+        galaxies
+            .iter()
+            .map(|galaxy| galaxy.row + max_col - galaxy.col)
+            .collect(),
+    );
+    sum / 2
+}
+
+/// Sum of the Euclidean distances between all pairs of galaxies.
+pub fn sum_euclidean_distances(galaxies: &[Galaxy]) -> f64 {
+    let mut distances = 0.0;
+    for (i, galaxy) in galaxies.iter().enumerate() {
+        for other in &galaxies[i + 1..] {
+            distances += galaxy.euclidean_distance(other);
         }
     }
-    expansions
+    distances
 }
 
-pub fn parse_file(input_file: &str, multiplier: usize) -> Vec<Galaxy> {
-    let mut galaxies: Vec<Galaxy> = Vec::new();
+/// Sorted indices of the rows or columns of a map holding at least one galaxy.
+#[derive(Debug, Default, PartialEq)]
+pub struct Occupancy {
+    indices: Vec<usize>,
+}
 
-    let data: Vec<Vec<char>> = read_to_string(input_file)
-        .unwrap()
-        .lines()
-        .map(|line| line.chars().collect())
-        .collect();
-    let expansions_columns = find_expansion_offsets_columns(&data, multiplier);
-    let expansions_rows = find_expansion_offsets_rows(&data, multiplier);
+impl FromIterator<usize> for Occupancy {
+    fn from_iter<T: IntoIterator<Item = usize>>(iter: T) -> Self {
+        let mut indices = iter.into_iter().collect::<Vec<usize>>();
+        indices.sort_unstable();
+        indices.dedup();
+        Occupancy { indices }
+    }
+}
 
-    for (line, row) in data.iter().zip(expansions_rows) {
-        for (char, col) in line.iter().zip(expansions_columns.clone()) {
-            if *char == '#' {
-                galaxies.push(Galaxy {
-                    id: galaxies.len() as i64 + 1,
-                    row,
-                    col,
-                })
-            }
+impl Occupancy {
+    /// Coordinate of every index once each empty row or column before it is expanded to
+    /// `multiplier` of them, from the number of occupied ones before it.
+    pub fn expander(&self, multiplier: usize) -> impl Fn(usize) -> usize + '_ {
+        move |i| {
+            let occupied = self.indices.partition_point(|&index| index < i);
+            occupied + (i - occupied) * multiplier
         }
     }
-    galaxies
+}
+
+/// Galaxies of a map with the rows and columns holding them, read line by line without keeping
+/// the map, so that apart from the line being read, memory grows with the number of galaxies
+/// rather than with the size of the map.
+#[derive(Debug, Default, PartialEq)]
+pub struct Universe {
+    positions: Vec<(usize, usize)>,
+    rows: Occupancy,
+    cols: Occupancy,
+}
+
+impl Universe {
+    pub fn read(reader: impl BufRead) -> io::Result<Self> {
+        let mut positions = Vec::new();
+        for (row, line) in reader.split(b'\n').enumerate() {
+            let line = line?;
+            let cols = line.iter().enumerate().filter(|&(_, &cell)| cell == b'#');
+            positions.extend(cols.map(|(col, _)| (row, col)));
+        }
+        Ok(Universe {
+            rows: positions.iter().map(|&(row, _)| row).collect(),
+            cols: positions.iter().map(|&(_, col)| col).collect(),
+            positions,
+        })
+    }
+
+    /// Galaxies once every empty row and column is expanded to `row_multiplier` rows and
+    /// `col_multiplier` columns.
+    pub fn expand(&self, row_multiplier: usize, col_multiplier: usize) -> Vec<Galaxy> {
+        let expand_row = self.rows.expander(row_multiplier);
+        let expand_col = self.cols.expander(col_multiplier);
+        self.positions
+            .iter()
+            .enumerate()
+            .map(|(i, &(row, col))| Galaxy {
+                id: i as i64 + 1,
+                row: expand_row(row),
+                col: expand_col(col),
+            })
+            .collect()
+    }
+}
+
+pub fn parse_file(
+    input_file: &str,
+    row_multiplier: usize,
+    col_multiplier: usize,
+) -> io::Result<Vec<Galaxy>> {
+    let universe = Universe::read(BufReader::new(File::open(input_file)?))?;
+    Ok(universe.expand(row_multiplier, col_multiplier))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const MAP: &str = "....\n.#..\n..#.\n....\n.#..\n";
+
     #[test]
     fn test_find_expansion_rows() {
-        let map = vec![
-            vec!['.', '.', '.', '.'],
-            vec!['.', '#', '.', '.'],
-            vec!['.', '.', '#', '.'],
-            vec!['.', '.', '.', '.'],
-            vec!['.', '#', '.', '.'],
-        ];
-        let offsets = find_expansion_offsets_rows(&map, 5);
+        let universe = Universe::read(MAP.as_bytes()).unwrap();
+        let expand = universe.rows.expander(5);
+        let offsets = (0..5).map(expand).collect::<Vec<usize>>();
         assert_eq!(vec![0, 5, 6, 7, 12], offsets);
     }
 
     #[test]
     fn test_find_expansion_cols() {
-        let map = vec![
-            vec!['.', '.', '.', '.'],
-            vec!['.', '#', '.', '.'],
-            vec!['.', '.', '#', '.'],
-            vec!['.', '.', '.', '.'],
-            vec!['.', '#', '.', '.'],
-        ];
-        let offsets = find_expansion_offsets_columns(&map, 5);
+        let universe = Universe::read(MAP.as_bytes()).unwrap();
+        let expand = universe.cols.expander(5);
+        let offsets = (0..4).map(expand).collect::<Vec<usize>>();
         assert_eq!(vec![0, 5, 6, 7], offsets);
     }
 
+    #[test]
+    fn test_occupancy() {
+        let occupancy = [200, 3, 64, 3].into_iter().collect::<Occupancy>();
+        assert_eq!(occupancy.indices, vec![3, 64, 200]);
+        let expand = occupancy.expander(2);
+        assert_eq!(expand(3), 6);
+        assert_eq!(expand(64), 64 + 63);
+        assert_eq!(expand(200), 200 + 198);
+        assert_eq!(expand(300), 300 + 297);
+    }
+
+    struct BrokenReader;
+
+    impl io::Read for BrokenReader {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::other("broken"))
+        }
+    }
+
+    #[test]
+    fn test_read_errors() {
+        assert!(Universe::read(BufReader::new(BrokenReader)).is_err());
+        assert!(parse_file("inputs/missing.txt", 2, 2).is_err());
+    }
+
+    #[test]
+    fn test_expand_sparse_universe() {
+        let width = 1_000_000;
+        let mut map = String::new();
+        for (row, col) in [(0, 10), (2, width - 1), (5, 0)] {
+            while map.lines().count() < row {
+                map += &".".repeat(width);
+                map += "\n";
+            }
+            map += &format!("{}#{}\n", ".".repeat(col), ".".repeat(width - col - 1));
+        }
+        let universe = Universe::read(map.as_bytes()).unwrap();
+        assert_eq!(universe.positions, vec![(0, 10), (2, width - 1), (5, 0)]);
+        let galaxies = universe.expand(10, 1);
+        assert_eq!(
+            galaxies
+                .iter()
+                .map(|galaxy| (galaxy.row, galaxy.col))
+                .collect::<Vec<_>>(),
+            vec![(0, 10), (11, width - 1), (32, 0)]
+        );
+    }
+
+    #[test]
+    fn test_sum_distances() {
+        // Galaxies of the example, once expanded.
+        let positions = [
+            (0, 4),
+            (1, 9),
+            (2, 0),
+            (5, 8),
+            (6, 1),
+            (7, 12),
+            (10, 9),
+            (11, 0),
+            (11, 5),
+        ];
+        let galaxies = positions
+            .iter()
+            .enumerate()
+            .map(|(i, &(row, col))| Galaxy {
+                id: i as i64 + 1,
+                row,
+                col,
+            })
+            .collect::<Vec<Galaxy>>();
+        assert_eq!(sum_distances(&galaxies), 374);
+        assert_eq!(sum_distances_brute_force(&galaxies), 374);
+        assert_eq!(sum_distances(&[]), 0);
+    }
+
+    #[test]
+    fn test_sum_distances_against_brute_force() {
+        let mut seed: u64 = 42;
+        let mut next = || {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 33) as usize % 1000
+        };
+        let galaxies = (0..500)
+            .map(|id| Galaxy {
+                id,
+                row: next(),
+                col: next(),
+            })
+            .collect::<Vec<Galaxy>>();
+        assert_eq!(
+            sum_distances(&galaxies),
+            sum_distances_brute_force(&galaxies)
+        );
+    }
+
+    #[test]
+    fn test_sum_distances_beyond_u64() {
+        let far = usize::MAX / 2;
+        let values = [vec![0; 1000], vec![far; 1000]].concat();
+        assert_eq!(sum_axis_distances(values), 1000 * 1000 * far as u128);
+    }
+
+    #[test]
+    fn test_metrics() {
+        let galaxy = |id, row, col| Galaxy { id, row, col };
+        let galaxies = vec![
+            galaxy(1, 0, 0),
+            galaxy(2, 3, 4),
+            galaxy(3, 5, 4),
+            galaxy(4, 1, 7),
+        ];
+        assert_eq!(galaxies[0].distance(&galaxies[1]), 7);
+        assert_eq!(galaxies[0].chebyshev_distance(&galaxies[1]), 4);
+        assert_eq!(galaxies[0].euclidean_distance(&galaxies[1]), 5.0);
+        assert_eq!(
+            distance_between(&galaxies, 1, 2, Metric::Euclidean),
+            Some(5.0)
+        );
+        assert_eq!(distance_between(&galaxies, 1, 5, Metric::Manhattan), None);
+
+        let brute_force_chebyshev = galaxies
+            .iter()
+            .enumerate()
+            .flat_map(|(i, galaxy)| {
+                galaxies[i + 1..]
+                    .iter()
+                    .map(|other| galaxy.chebyshev_distance(other) as u128)
+            })
+            .sum::<u128>();
+        assert_eq!(sum_chebyshev_distances(&galaxies), brute_force_chebyshev);
+        assert_eq!(sum_chebyshev_distances(&galaxies), 4 + 5 + 7 + 2 + 3 + 4);
+        assert_eq!(sum_euclidean_distances(&galaxies[..2]), 5.0);
+    }
+
+    #[test]
+    fn test_nearest_neighbours() {
+        let galaxy = |id, row, col| Galaxy { id, row, col };
+        let galaxies = vec![
+            galaxy(1, 0, 0),
+            galaxy(2, 3, 4),
+            galaxy(3, 5, 4),
+            galaxy(4, 0, 6),
+        ];
+        let nearest = |metric| {
+            nearest_neighbours(&galaxies, metric)
+                .into_iter()
+                .map(|neighbour| neighbour.map(|(galaxy, distance)| (galaxy.id(), distance)))
+                .collect::<Vec<Option<(i64, f64)>>>()
+        };
+        assert_eq!(
+            nearest(Metric::Manhattan),
+            vec![
+                Some((4, 6.0)),
+                Some((3, 2.0)),
+                Some((2, 2.0)),
+                Some((2, 5.0))
+            ]
+        );
+        assert_eq!(
+            nearest(Metric::Chebyshev),
+            vec![
+                Some((2, 4.0)),
+                Some((3, 2.0)),
+                Some((2, 2.0)),
+                Some((2, 3.0))
+            ]
+        );
+        assert_eq!(
+            nearest_neighbours(&galaxies[..1], Metric::Euclidean),
+            vec![None]
+        );
+    }
+
     #[test]
     fn test_parse_file() {
-        let galaxies = parse_file("inputs/input_test_1.txt", 2);
+        let galaxies = parse_file("inputs/input_test_1.txt", 2, 2).unwrap();
         let exp_galaxies = vec![
             Galaxy {
                 id: 1,
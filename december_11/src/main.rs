@@ -1,8 +1,12 @@
 mod galaxy;
 
+use std::io;
 use std::time::Instant;
 use clap::{Parser, ValueEnum};
-use crate::galaxy::parse_file;
+use crate::galaxy::{
+    distance_between, nearest_neighbours, parse_file, sum_chebyshev_distances, sum_distances,
+    sum_distances_brute_force, sum_euclidean_distances, Galaxy, Metric,
+};
 
 #[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Tasks {
@@ -17,39 +21,112 @@ pub struct Cli {
     /// Number of lines to read
     #[structopt(short = 't')]
     task: Tasks,
+    /// Sum the Manhattan distances by comparing every pair of galaxies
+    #[arg(long)]
+    brute_force: bool,
+    /// Number of rows or columns each empty one expands to, overriding the factor of the task
+    #[arg(long)]
+    factor: Option<usize>,
+    /// Number of rows each empty row expands to, overriding --factor
+    #[arg(long)]
+    row_factor: Option<usize>,
+    /// Number of columns each empty column expands to, overriding --factor
+    #[arg(long)]
+    col_factor: Option<usize>,
+    /// How distances between galaxies are measured
+    #[arg(long, value_enum, default_value_t = Metric::Manhattan)]
+    metric: Metric,
+    /// Print the distance between the two galaxies with these ids instead of the sum
+    #[arg(long, num_args = 2, value_names = ["ID", "OTHER_ID"])]
+    between: Option<Vec<i64>>,
+    /// Print the nearest neighbour of every galaxy instead of the sum
+    #[arg(long)]
+    nearest: bool,
 }
 
-fn task_1(input_file: &str) -> u64 {
-    let galaxies = parse_file(input_file, 1);
-    let mut distances = 0;
-    for i in 0..galaxies.len() {
-        for j in (i+1)..galaxies.len() {
-            distances += galaxies[i].distance(&galaxies[j]);
-        }
+/// Sum of the distances between all pairs of galaxies.
+fn sum_all_distances(galaxies: &[Galaxy], metric: Metric, brute_force: bool) -> String {
+    match metric {
+        Metric::Manhattan if brute_force => sum_distances_brute_force(galaxies).to_string(),
+        Metric::Manhattan => sum_distances(galaxies).to_string(),
+        Metric::Chebyshev => sum_chebyshev_distances(galaxies).to_string(),
+        Metric::Euclidean => sum_euclidean_distances(galaxies).to_string(),
     }
-    distances
 }
 
-fn task_2(input_file: &str) -> u64 {
-    let galaxies = parse_file(input_file, 1000000);
-    let mut distances = 0;
-    for i in 0..galaxies.len() {
-        for j in (i+1)..galaxies.len() {
-            distances += galaxies[i].distance(&galaxies[j]);
-        }
-    }
-    distances
+fn run(
+    input_file: &str,
+    row_factor: usize,
+    col_factor: usize,
+    metric: Metric,
+    brute_force: bool,
+) -> io::Result<String> {
+    let galaxies = parse_file(input_file, row_factor, col_factor)?;
+    Ok(sum_all_distances(&galaxies, metric, brute_force))
 }
 
+/// Exits with the error when the input cannot be read.
+fn exit_on_error<T>(input_file: &str, result: io::Result<T>) -> T {
+    result.unwrap_or_else(|error| {
+        eprintln!("cannot read {}: {}", input_file, error);
+        std::process::exit(1);
+    })
+}
 
 fn main() {
     let args = Cli::parse();
+    let factor = args.factor.unwrap_or(match args.task {
+        Tasks::Task1 => 2,
+        Tasks::Task2 => 1000000,
+    });
+    let row_factor = args.row_factor.unwrap_or(factor);
+    let col_factor = args.col_factor.unwrap_or(factor);
+
+    if let Some(ids) = &args.between {
+        let galaxies = exit_on_error(
+            &args.input_file,
+            parse_file(&args.input_file, row_factor, col_factor),
+        );
+        match distance_between(&galaxies, ids[0], ids[1], args.metric) {
+            Some(distance) => {
+                println!("Distance between {} and {} is {}", ids[0], ids[1], distance)
+            }
+            None => println!("Unknown galaxy among {} and {}", ids[0], ids[1]),
+        }
+        return;
+    }
+
+    if args.nearest {
+        let galaxies = exit_on_error(
+            &args.input_file,
+            parse_file(&args.input_file, row_factor, col_factor),
+        );
+        for (galaxy, neighbour) in galaxies
+            .iter()
+            .zip(nearest_neighbours(&galaxies, args.metric))
+        {
+            match neighbour {
+                Some((other, distance)) => println!(
+                    "Nearest to {} is {} at {}",
+                    galaxy.id(),
+                    other.id(),
+                    distance
+                ),
+                None => println!("{} has no neighbour", galaxy.id()),
+            }
+        }
+        return;
+    }
 
     let start = Instant::now();
-    let result = match args.task {
-        Tasks::Task1 => task_1(&args.input_file),
-        Tasks::Task2 => task_2(&args.input_file),
-    };
+    let result = run(
+        &args.input_file,
+        row_factor,
+        col_factor,
+        args.metric,
+        args.brute_force,
+    );
+    let result = exit_on_error(&args.input_file, result);
     println!(
         "The task took {}ms to complete",
         start.elapsed().as_nanos() as f32 / 1_000_000.0
@@ -63,11 +140,17 @@ mod tests {
 
     #[test]
     fn test_task_1() {
-        assert_eq!(task_1("inputs/input_test_1.txt"), 374);
+        assert_eq!(
+            run("inputs/input_test_1.txt", 2, 2, Metric::Manhattan, false).unwrap(),
+            "374"
+        );
     }
 
     #[test]
     fn test_task_2() {
-        assert_eq!(task_2("inputs/input_test_1.txt"), 1030);
+        assert_eq!(
+            run("inputs/input_test_1.txt", 10, 10, Metric::Manhattan, false).unwrap(),
+            "1030"
+        );
     }
-}
\ No newline at end of file
+}